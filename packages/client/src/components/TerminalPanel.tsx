@@ -36,6 +36,7 @@ import { useTooltipMenu } from '@/hooks/use-tooltip-menu';
 import { getActiveWS } from '@/hooks/use-ws';
 import { createAnsiConverter } from '@/lib/ansi-to-html';
 import { api } from '@/lib/api';
+import { decodePtyOutput } from '@/lib/pty-output';
 import { cn } from '@/lib/utils';
 import { useProjectStore } from '@/stores/project-store';
 import { type TerminalShell, useSettingsStore, EDITOR_FONT_SIZE_PX } from '@/stores/settings-store';
@@ -186,8 +187,8 @@ function TauriTerminalTabContent({
       const { listen } = await import('@tauri-apps/api/event');
       if (!isMounted) return;
 
-      const unlistenData = await listen<{ data: string }>(`pty:data:${id}`, (event) => {
-        terminal.write(event.payload.data);
+      const unlistenData = await listen<string>(`pty://output/${id}`, (event) => {
+        terminal.write(decodePtyOutput(event.payload));
         useTerminalStore.getState().markAlive(id);
       });
 
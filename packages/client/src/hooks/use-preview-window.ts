@@ -1,4 +1,5 @@
-import { useCallback } from 'react';
+import { useCallback, useEffect } from 'react';
+import { useNavigate } from 'react-router-dom';
 
 import { usePreviewStore } from '@/stores/preview-store';
 
@@ -36,6 +37,32 @@ if (isTauri && !isPreviewWindow) {
   })();
 }
 
+/**
+ * In a preview window opened at an app route, follow the `preview:navigate`
+ * events `open_preview_url` sends when it is asked to show another path.
+ */
+export function usePreviewNavigation() {
+  const navigate = useNavigate();
+
+  useEffect(() => {
+    if (!isTauri || !isPreviewWindow) return;
+
+    let unlisten: (() => void) | null = null;
+    let isMounted = true;
+    (async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const fn = await listen<string>('preview:navigate', (e) => navigate(e.payload));
+      if (isMounted) unlisten = fn;
+      else fn();
+    })();
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, [navigate]);
+}
+
 export function usePreviewWindow() {
   const openPreview = useCallback(
     async (opts: { commandId: string; projectId: string; port: number; commandLabel: string }) => {
@@ -3,6 +3,7 @@ import { WebLinksAddon } from '@xterm/addon-web-links';
 import { Terminal } from '@xterm/xterm';
 import { useEffect, useRef } from 'react';
 
+import { decodePtyOutput } from '@/lib/pty-output';
 import { useSettingsStore, EDITOR_FONT_SIZE_PX } from '@/stores/settings-store';
 import { useTerminalStore } from '@/stores/terminal-store';
 
@@ -97,8 +98,8 @@ export function useTerminal({ id, cwd, containerRef }: UseTerminalOptions) {
       if (!isMounted) return;
 
       // Listen for PTY output
-      const unlistenData = await listen<{ data: string }>(`pty:data:${id}`, (event) => {
-        terminal.write(event.payload.data);
+      const unlistenData = await listen<string>(`pty://output/${id}`, (event) => {
+        terminal.write(decodePtyOutput(event.payload));
       });
 
       // Listen for PTY exit
@@ -0,0 +1,13 @@
+/**
+ * Decode the payload of a `pty://output/<id>` event: raw PTY bytes, base64
+ * encoded. xterm accepts the bytes directly and reassembles UTF-8 sequences
+ * split across events.
+ */
+export function decodePtyOutput(payload: string): Uint8Array {
+  const binary = atob(payload);
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+}
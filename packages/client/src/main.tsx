@@ -24,6 +24,7 @@ import { BrowserRouter } from 'react-router-dom';
 
 import { AppShellSkeleton } from './components/AppShellSkeleton';
 import { TooltipProvider } from './components/ui/tooltip';
+import { usePreviewNavigation } from './hooks/use-preview-window';
 import { api } from './lib/api';
 import { useAuthStore } from './stores/auth-store';
 import { useProfileStore } from './stores/profile-store';
@@ -57,6 +58,14 @@ const SetupWizard = lazy(() =>
 // The preview window sets this flag via Tauri's initialization_script
 const isPreviewWindow = !!(window as unknown as { __PREVIEW_MODE__: unknown }).__PREVIEW_MODE__;
 
+// Preview windows opened at an app route (`open_preview_url`) render the app
+// there instead of the preview tab browser. The route is applied before the
+// router mounts so it starts on it.
+const previewUrl = (window as unknown as { __PREVIEW_URL__?: string }).__PREVIEW_URL__;
+if (previewUrl) {
+  window.history.replaceState(null, '', previewUrl);
+}
+
 // Matches Tailwind's `md` breakpoint (768px)
 const mobileQuery = window.matchMedia('(max-width: 767px)');
 const subscribe = (cb: () => void) => {
@@ -67,6 +76,7 @@ const getSnapshot = () => mobileQuery.matches;
 
 function ResponsiveShell() {
   const isMobile = useSyncExternalStore(subscribe, getSnapshot);
+  usePreviewNavigation();
   return <Suspense fallback={<AppShellSkeleton />}>{isMobile ? <MobilePage /> : <App />}</Suspense>;
 }
 
@@ -246,7 +256,7 @@ root.render(
         }}
       >
         <TooltipProvider delayDuration={300} skipDelayDuration={0}>
-          {isPreviewWindow ? (
+          {isPreviewWindow && !previewUrl ? (
             <Suspense fallback={null}>
               <PreviewBrowser />
             </Suspense>
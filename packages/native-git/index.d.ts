@@ -1,16 +1,241 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * Apply `stash@{index}` to the worktree, restoring its staged changes to
+ * the index as well, like `git stash apply --index`. With `pop`, the entry
+ * is dropped afterwards.
+ *
+ * Stashed changes that clash with local ones are merged: conflicting paths
+ * are left as unmerged index entries with conflict markers, an error is
+ * returned, and the entry is kept even with `pop`. Nothing is touched when
+ * the stash would overwrite uncommitted changes or untracked files.
+ */
+export declare function applyStash(
+  cwd: string,
+  index: number,
+  pop?: boolean | undefined | null,
+): Promise<void>;
+
+/**
+ * Summarize many worktrees in one call, scanning them in parallel. Results
+ * are returned in request order; a failure for one worktree is reported in
+ * its `error` field instead of failing the whole batch.
+ */
+export declare function batchGetStatusSummary(
+  requests: Array<StatusSummaryRequest>,
+): Promise<Array<BatchStatusResult>>;
+
+export interface BatchStatusResult {
+  worktreeCwd: string;
+  result?: GitStatusSummary;
+  error?: string;
+}
+
 export interface BranchDetailedInfo {
   name: string;
   isLocal: boolean;
   isRemote: boolean;
 }
 
+/**
+ * Names and statuses of the files changed between two revisions, without
+ * line counts: the cheap counterpart to `diff_refs`. Renames are detected
+ * at git's default 50% similarity and reported as `renamed` under the new
+ * path.
+ */
+export declare function changedFilesBetween(
+  cwd: string,
+  fromRef: string,
+  toRef: string,
+): Promise<Array<FileDiffSummaryItem>>;
+
+/**
+ * Invalidate cached `get_status_summary` results for `cwd`.
+ *
+ * Call after operations that change the worktree or refs (commit, stage,
+ * checkout, ...) so the next status query recomputes instead of returning
+ * a result up to one TTL old.
+ */
+export declare function clearStatusCache(cwd: string): void;
+
+export interface CommitDetails {
+  hash: string;
+  shortHash: string;
+  authorName: string;
+  authorEmail: string;
+  committerName: string;
+  committerEmail: string;
+  /** Author date, in seconds since the Unix epoch. */
+  timestampSecs: number;
+  /** Subject line. */
+  message: string;
+  /** Everything after the subject line, trimmed. */
+  body: string;
+  parentHashes: Array<string>;
+}
+
+export interface CommitDiffEntry {
+  path: string;
+  /** Source path for `renamed` entries. */
+  previousPath?: string;
+  status: string;
+}
+
 export interface CommitFileEntry {
   path: string;
   status: string;
   additions: number;
   deletions: number;
+  /**
+   * Tracked with Git LFS (`filter=lfs`, per the current `.gitattributes`);
+   * its lines are not counted.
+   */
+  lfs: boolean;
+}
+
+export interface CommitGraph {
+  commits: Array<GraphCommit>;
+}
+
+export interface CommitGraphInfo {
+  exists: boolean;
+  path: string;
+  /** Commits in the graph, or `None` if the file couldn't be parsed. */
+  commitCount?: number;
+}
+
+export interface CommitStats {
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+}
+
+export interface ConflictMarkerCount {
+  path: string;
+  markerCount: number;
+}
+
+export interface Contributor {
+  /** Name and email as spelled on the contributor's most recent commit. */
+  name: string;
+  email: string;
+  commitCount: number;
+}
+
+export interface ContributorSummary {
+  author: string;
+  commitCount: number;
+  linesAdded: number;
+  linesDeleted: number;
+  /**
+   * Author date of their most recent commit to the file, in seconds since
+   * the Unix epoch.
+   */
+  lastCommitTimestamp: number;
+}
+
+/**
+ * For each path still in conflict in the index (entries at stages 1-3),
+ * count the conflict markers left in its worktree file.
+ *
+ * A zero count for a path that is still listed means its content has been
+ * resolved but not yet staged with `git add`.
+ */
+export declare function countConflictMarkers(cwd: string): Promise<Array<ConflictMarkerCount>>;
+
+/**
+ * Stash the worktree and index changes, like `git stash push`.
+ *
+ * Records the stash commit under `refs/stash` (with the usual
+ * `On <branch>: <message>` reflog entry, or `WIP on <branch>: ...` without a
+ * message) and resets the worktree and index to HEAD. With
+ * `include_untracked`, untracked files are stashed and removed too. Returns
+ * the new stash commit hash; fails if there is nothing to stash.
+ */
+export declare function createStash(
+  cwd: string,
+  message?: string | undefined | null,
+  includeUntracked?: boolean | undefined | null,
+): Promise<string>;
+
+/**
+ * Create tag `name` pointing at `target_ref` (default HEAD) and return the
+ * hash of the object it was resolved to. Without `message` the tag is
+ * lightweight; with one it is annotated, using the configured committer
+ * identity as tagger. Fails if the tag already exists.
+ */
+export declare function createTag(
+  cwd: string,
+  name: string,
+  targetRef?: string | undefined | null,
+  message?: string | undefined | null,
+): Promise<string>;
+
+/** Delete `refs/tags/<name>`. */
+export declare function deleteTag(cwd: string, name: string): Promise<void>;
+
+/**
+ * Name HEAD after the closest tag reachable from it, like `git describe`:
+ * `<tag>-<commits since tag>-g<abbreviated hash>`, or just `<tag>` when HEAD
+ * is tagged. Only annotated tags are candidates unless `include_lightweight`
+ * is set (`git describe --tags`). Without a reachable tag, the abbreviated
+ * HEAD hash is returned.
+ */
+export declare function describe(
+  cwd: string,
+  includeLightweight?: boolean | undefined | null,
+): Promise<string>;
+
+/** Optional settings for the file diff functions. */
+export interface DiffOptions {
+  /**
+   * Render changed regions as a word diff in `git diff --word-diff=porcelain`
+   * format instead of whole `-`/`+` lines.
+   */
+  wordDiff?: boolean;
+  /** `"histogram"` (default), `"myers"` or `"minimal"`. */
+  diffAlgorithm?: string;
+  /**
+   * Unchanged lines shown around each change (default 3, at most 65535).
+   * Ignored by `get_full_context_file_diff`.
+   */
+  contextLines?: number;
+  /**
+   * Whitespace differences to disregard when matching lines. The output
+   * still shows the original lines.
+   */
+  ignoreWhitespace?: WhitespaceMode;
+  /**
+   * Rename detection threshold in percent (0-100, default 50) for
+   * `get_diff_between_commits`; 0 disables rename detection.
+   */
+  renameSimilarity?: number;
+}
+
+/**
+ * Per-file line counts between two revisions, the native analog of
+ * `git diff --numstat <from_ref> <to_ref>` (without rename detection).
+ * Binary and Git LFS files are flagged with zero counts. Paths containing
+ * any of `exclude_patterns` are left out.
+ */
+export declare function diffRefs(
+  cwd: string,
+  fromRef: string,
+  toRef: string,
+  excludePatterns?: Array<string> | undefined | null,
+): Promise<Array<FileDiffStat>>;
+
+export interface DiffSummaryOptions {
+  /**
+   * Compare the worktree against this revision's tree instead of reporting
+   * unstaged and staged changes relative to the index and HEAD.
+   */
+  baseRev?: string;
+  /**
+   * Only report files at or under this repo-relative path, e.g.
+   * `packages/client`. Taken literally: glob characters match themselves.
+   */
+  pathPrefix?: string;
 }
 
 export interface DiffSummaryResult {
@@ -19,60 +244,372 @@ export interface DiffSummaryResult {
   truncated: boolean;
 }
 
+/** `discard_changes` for every tracked file that differs from the index. */
+export declare function discardAllChanges(cwd: string): Promise<void>;
+
+/**
+ * Discard unstaged changes to `paths` (repo-relative) by writing back their
+ * index version, like `git restore -- <paths>`. Deleted files are
+ * re-created. Untracked and unmerged paths, directories and submodules are
+ * skipped, and nothing is deleted. A path that fails to restore doesn't
+ * stop the others. Returns the paths restored.
+ */
+export declare function discardChanges(
+  cwd: string,
+  paths: Array<string>,
+): Promise<Array<string>>;
+
+/**
+ * Remove `stash@{index}`. The stash reflog is rewritten without the entry,
+ * so older entries move up one index.
+ */
+export declare function dropStash(cwd: string, index: number): Promise<void>;
+
+export interface FileDiffStat {
+  path: string;
+  status: string;
+  added: number;
+  deleted: number;
+  binary: boolean;
+  /** Tracked with Git LFS (`filter=lfs`); counted as binary. */
+  lfs: boolean;
+}
+
 export interface FileDiffSummaryItem {
   path: string;
   status: string;
   staged: boolean;
 }
 
+export interface GetBranchesResult {
+  branches: Array<string>;
+  isDetached: boolean;
+  /** Full hash of the commit HEAD points at when detached. */
+  detachedHash?: string;
+}
+
+/**
+ * Commits reachable from HEAD and `include_refs` (newest first, at most
+ * `limit`, default 500) with parent edges and lane assignments for drawing
+ * a branch graph.
+ */
+export declare function getBranchGraph(
+  cwd: string,
+  limit?: number | undefined | null,
+  includeRefs?: Array<string> | undefined | null,
+): Promise<CommitGraph>;
+
+/**
+ * Paths that would conflict if `commit_hash` were cherry-picked onto HEAD,
+ * or an empty list if it would apply cleanly.
+ *
+ * Runs a three-way tree merge of the commit's first parent (base), HEAD
+ * (ours) and the commit (theirs) entirely in memory: nothing is written to
+ * the object database, index or worktree.
+ */
+export declare function getCherryPickConflicts(cwd: string, commitHash: string): Promise<Array<string>>;
+
+/**
+ * Full details of a single commit. `hash` can be anything `rev-parse`
+ * accepts (full or short hash, branch, tag, `HEAD~2`, ...); a revision that
+ * doesn't resolve to a commit fails with "Commit not found".
+ */
+export declare function getCommit(cwd: string, hash: string): Promise<CommitDetails>;
+
 export declare function getCommitBody(cwd: string, hash: string): Promise<string>;
 
 export declare function getCommitFileDiff(
   cwd: string,
   hash: string,
   filePath: string,
+  options?: DiffOptions | undefined | null,
 ): Promise<string>;
 
 export declare function getCommitFiles(cwd: string, hash: string): Promise<Array<CommitFileEntry>>;
 
+/**
+ * Whether the repository has a commit-graph file
+ * (`objects/info/commit-graph`), which speeds up history walks, and how
+ * many commits it covers. Split graphs (`objects/info/commit-graphs/`) are
+ * not considered.
+ */
+export declare function getCommitGraphPresence(cwd: string): Promise<CommitGraphInfo>;
+
+/**
+ * Totals of `get_commit_files`, like the `git show --shortstat` line: the
+ * commit against its first parent (the empty tree for root commits, the
+ * first parent only for merges). Binary and Git LFS files count as changed
+ * files without lines.
+ */
+export declare function getCommitStats(cwd: string, hash: string): Promise<CommitStats>;
+
+/**
+ * Effective value of config `key` (dotted, e.g. `core.autocrlf` or
+ * `branch.main.remote`), with local config overriding global and global
+ * overriding system. `None` if the key isn't set.
+ */
+export declare function getConfig(cwd: string, key: string): Promise<string | null>;
+
+/**
+ * The author identity git would use for a commit in `cwd`:
+ * `GIT_AUTHOR_NAME` / `GIT_AUTHOR_EMAIL` if set, else `user.name` /
+ * `user.email` from the merged system, global and local config. Each field
+ * is `None` independently when it isn't configured.
+ */
+export declare function getConfigUser(cwd: string): Promise<UserIdentity>;
+
+/**
+ * Commit authors in HEAD's history, most commits first (ties by name),
+ * grouped by case-insensitive email so different spellings of one person's
+ * name are counted together. With `since_secs` (Unix seconds), only commits
+ * committed at or after that time count. At most the newest 50000 commits
+ * are looked at.
+ */
+export declare function getContributors(
+  cwd: string,
+  sinceSecs?: number | undefined | null,
+): Promise<Array<Contributor>>;
+
+/**
+ * Name of the checked-out branch. On a detached HEAD this falls back to the
+ * short hash of the commit HEAD points at; prefer `get_head_info`, which
+ * tells the two apart. `None` only before the first commit.
+ */
 export declare function getCurrentBranch(cwd: string): Promise<string | null>;
 
 export declare function getDefaultBranch(cwd: string): Promise<string | null>;
 
+/**
+ * Files changed between two commits (any revisions that resolve to commits).
+ *
+ * Renames are detected at `options.rename_similarity` percent (0-100,
+ * default 50 like git): 0 disables rename detection so renames show up as a
+ * deletion plus an addition, and 100 only pairs files with identical content.
+ */
+export declare function getDiffBetweenCommits(
+  cwd: string,
+  from: string,
+  to: string,
+  options?: DiffOptions | undefined | null,
+): Promise<Array<CommitDiffEntry>>;
+
+/**
+ * Summarize the changed files in the worktree at `cwd`: unstaged changes
+ * and untracked files (`staged: false`), then staged changes relative to
+ * HEAD (`staged: true`).
+ *
+ * With `options.base_rev`, the worktree is instead compared file by file
+ * against that revision's tree, e.g. to show everything changed since
+ * branching off `main`, committed or not. Entries are then `added`,
+ * `modified`, `deleted` or `conflicted`, sorted by path, and all
+ * `staged: false`.
+ *
+ * With `options.path_prefix`, only files under that path are scanned and
+ * reported.
+ */
 export declare function getDiffSummary(
   cwd: string,
   excludePatterns?: Array<string> | undefined | null,
   maxFiles?: number | undefined | null,
+  options?: DiffSummaryOptions | undefined | null,
 ): Promise<DiffSummaryResult>;
 
+/**
+ * Authors of the commits in HEAD's history that changed `rel_path`, most
+ * commits first, with the lines each added and deleted (binary versions
+ * count no lines). Authors are grouped by name. A merge counts only if the
+ * file differs from every parent, as in `git log -- <path>`, and its lines
+ * are counted against the first parent. Renames are not followed. At most
+ * the newest 500 commits are looked at.
+ */
+export declare function getFileContributors(
+  cwd: string,
+  relPath: string,
+): Promise<Array<ContributorSummary>>;
+
+/**
+ * List the entries directly under `prefix` (the root when `None`) in the
+ * tree of `rev`, one level deep. Blob sizes come from the object header,
+ * so file contents are never read.
+ */
+export declare function getFileTree(
+  cwd: string,
+  rev: string,
+  prefix?: string | undefined | null,
+): Promise<Array<TreeEntry>>;
+
 /** Full-context diff (equivalent to git diff -U99999). Shows all lines of the file. */
 export declare function getFullContextFileDiff(
   cwd: string,
   filePath: string,
   staged: boolean,
+  options?: DiffOptions | undefined | null,
 ): Promise<string>;
 
+/**
+ * `.gitattributes` state of `attribute_names` for each of `paths`
+ * (repo-relative), like `git check-attr`. Macros are expanded, so e.g.
+ * `binary` reports `text` and `diff` as unset. Paths don't need to exist.
+ */
+export declare function getGitattributes(
+  cwd: string,
+  paths: Array<string>,
+  attributeNames: Array<string>,
+): Promise<Array<PathAttributes>>;
+
+/**
+ * The checked-out branch (if any) and the commit HEAD resolves to. Fails
+ * before the first commit, when HEAD doesn't point at a commit yet.
+ */
+export declare function getHeadInfo(cwd: string): Promise<HeadInfo>;
+
+/**
+ * Files in the repository's hooks directories: `.git/hooks` and, when set,
+ * `core.hooksPath` (relative paths resolve against the worktree root, as in
+ * git). Every file is listed whatever its name, including `*.sample`
+ * files; git only runs executable files named after a hook from the active
+ * directory (`core.hooksPath` if set). Standard hooks come first.
+ */
+export declare function getHooks(cwd: string): Promise<Array<HookEntry>>;
+
+/**
+ * Summarize the staged changes: the HEAD tree compared against the index,
+ * i.e. what the next commit would contain. Complements `get_diff_summary`,
+ * whose entries are mostly index-to-worktree changes. Every item is
+ * `staged: true`; paths with several index entries (unresolved merges) are
+ * reported as `conflicted`.
+ */
+export declare function getIndexToHeadDiff(
+  cwd: string,
+  maxFiles?: number | undefined | null,
+): Promise<DiffSummaryResult>;
+
+/** The most recent commits reachable from HEAD (default 20). */
 export declare function getLog(
   cwd: string,
   limit?: number | undefined | null,
+  options?: GetLogOptions | undefined | null,
 ): Promise<Array<GitLogEntry>>;
 
+/**
+ * `get_log` with each entry's parent hashes, so the caller can lay out
+ * lanes and merge edges. Same walk and default limit (20) as `get_log`,
+ * which stays the cheaper choice when no graph is drawn. For lanes computed
+ * natively, see `get_branch_graph`.
+ */
+export declare function getLogGraph(
+  cwd: string,
+  limit?: number | undefined | null,
+): Promise<Array<LogGraphEntry>>;
+
+export interface GetLogOptions {
+  /**
+   * Order commits like `git log --topo-order`: every commit before its
+   * parents, and the commits of a merged branch kept together. Default is
+   * the plain walk order.
+   */
+  topologicalOrder?: boolean;
+}
+
+/**
+ * One page of `get_log`: up to `page_size` commits following `cursor` (the
+ * hash of the last commit of the previous page) in HEAD's history, or the
+ * first page without a cursor. Nothing is kept between calls: every page
+ * walks from HEAD and skips commits up to the cursor, so it costs O(n) in
+ * the number of commits before it.
+ */
+export declare function getLogPage(
+  cwd: string,
+  cursor: string | undefined | null,
+  pageSize: number,
+): Promise<LogPage>;
+
+/**
+ * Size in bytes of object `hash` (full or abbreviated), read from the
+ * object header so blob contents are not loaded.
+ */
+export declare function getObjectSize(cwd: string, hash: string): Promise<bigint>;
+
+/**
+ * The operation in progress in the worktree at `cwd`, detected like git
+ * does from the state files in its git dir. A rebase takes precedence over
+ * the merge or cherry-pick it may be running for one of its steps.
+ */
+export declare function getOperationState(cwd: string): Promise<OperationState>;
+
 export declare function getRemoteUrl(cwd: string): Promise<string | null>;
 
+/**
+ * Object storage statistics for repo health diagnostics, read straight from
+ * the `objects` directory (shared by all worktrees).
+ */
+export declare function getRepositoryPackStats(cwd: string): Promise<PackStats>;
+
+/**
+ * `user.name` and `user.email` from the merged config. Unlike
+ * `get_config_user`, `GIT_AUTHOR_*` environment variables are ignored.
+ */
+export declare function getSignature(cwd: string): Promise<UserIdentity>;
+
 export declare function getSingleFileDiff(
   cwd: string,
   filePath: string,
   staged: boolean,
+  options?: DiffOptions | undefined | null,
 ): Promise<string>;
 
+/**
+ * Per-file line counts for the staged changes (HEAD tree vs index), the
+ * numbers `git diff --cached --numstat` reports. Binary files are flagged
+ * with zero counts, as are Git LFS files (also flagged `lfs`); unmerged paths
+ * are reported as `conflicted`.
+ */
+export declare function getStagedDiffStats(cwd: string): Promise<Array<FileDiffStat>>;
+
+/**
+ * Files changed by `stash@{stash_index}`: the stash commit compared
+ * against its first parent (the commit the stash was made on). Files from
+ * the untracked-files parent (`git stash -u`) are included as `added`.
+ */
+export declare function getStashDiff(cwd: string, stashIndex: number): Promise<DiffSummaryResult>;
+
+/**
+ * Summarize a worktree's status relative to its upstream and `base_branch`.
+ * The upstream is the branch's configured one (see `set_upstream`), or
+ * `origin/<branch>` when none is configured.
+ *
+ * Results are cached per worktree and set of options for
+ * `options.cache_ttl_ms`, so concurrent queries for the same worktree don't
+ * each rescan it. Use `clear_status_cache` to invalidate.
+ */
 export declare function getStatusSummary(
   worktreeCwd: string,
   baseBranch?: string | undefined | null,
   projectCwd?: string | undefined | null,
+  options?: StatusSummaryOptions | undefined | null,
 ): Promise<GitStatusSummary>;
 
+/**
+ * Resolve `tag_name` (short name or full `refs/tags/...`) to the commit it
+ * ultimately points at. Annotated tags, including tags of tags, are peeled
+ * all the way down; tags that end at a tree or blob are an error.
+ */
+export declare function getTagCommit(cwd: string, tagName: string): Promise<string>;
+
 export declare function getUnpushedHashes(cwd: string): Promise<Array<string>>;
 
+/**
+ * Untracked files in the worktree at `cwd` as a tree, built from the
+ * status scan rather than a separate directory walk. Each level lists
+ * directories first, then files, by name. With `max_depth`, directories
+ * at that depth (top-level entries are at depth 1) are returned without
+ * their contents.
+ */
+export declare function getUntrackedTree(
+  cwd: string,
+  maxDepth?: number | undefined | null,
+): Promise<Array<UntrackedNode>>;
+
 export interface GitLogEntry {
   hash: string;
   shortHash: string;
@@ -82,7 +619,23 @@ export interface GitLogEntry {
 }
 
 export interface GitStatusSummary {
+  /**
+   * `tracked_modified_count + untracked_file_count`. Deprecated: will be
+   * removed in a future version in favour of the two separate counts.
+   */
   dirtyFileCount: number;
+  /**
+   * Tracked files with staged or unstaged changes (including deletions and
+   * dirty submodules).
+   */
+  trackedModifiedCount: number;
+  untrackedFileCount: number;
+  /**
+   * Files whose index entry differs from HEAD (added, modified, deleted or
+   * unmerged), like `git diff --cached --name-only`. Only computed with
+   * `StatusSummaryOptions.include_staged`; 0 otherwise.
+   */
+  stagedFileCount: number;
   unpushedCommitCount: number;
   unpulledCommitCount: number;
   hasRemoteBranch: boolean;
@@ -91,11 +644,351 @@ export interface GitStatusSummary {
   linesDeleted: number;
 }
 
+/** A `GitLogEntry` plus the data needed to draw it in a commit graph. */
+export interface GraphCommit {
+  hash: string;
+  shortHash: string;
+  author: string;
+  relativeDate: string;
+  message: string;
+  parentHashes: Array<string>;
+  /** Lane the commit is drawn in, 0 being leftmost. */
+  column: number;
+  /**
+   * Lines joining this commit's lane with other lanes: branches that end
+   * here (`from_column` is the other lane) and merge parents that continue
+   * in another lane (`to_column` is the other lane).
+   */
+  mergeLines: Array<MergeLine>;
+}
+
+/**
+ * Reset the current branch (or detached HEAD), the index and the worktree
+ * to the commit `target_rev` resolves to, like `git reset --hard`. Fails if
+ * tracked files have uncommitted changes unless `options.force` is set, in
+ * which case they are discarded; untracked files are left alone.
+ */
+export declare function hardReset(
+  cwd: string,
+  targetRev: string,
+  options?: ResetOptions | undefined | null,
+): Promise<void>;
+
+export interface HeadInfo {
+  /** Checked-out branch, `None` when HEAD is detached. */
+  branch?: string;
+  commitHash: string;
+  isDetached: boolean;
+}
+
+export interface HookEntry {
+  name: string;
+  path: string;
+  isExecutable: boolean;
+  sizeBytes: bigint;
+}
+
+/**
+ * True if blob `hash` looks binary (a NUL byte in its first 8KB), the same
+ * check the status and diff functions use.
+ */
+export declare function isBinaryBlob(cwd: string, hash: string): Promise<boolean>;
+
+/**
+ * Whether the worktree has any change `get_status_summary` would count:
+ * staged changes, modified or deleted tracked files, or untracked files.
+ * Stops at the first change found and skips line counting and branch
+ * analysis, so it's much cheaper than the full summary.
+ */
+export declare function isDirty(cwd: string): Promise<boolean>;
+
+/** Whether `normalize_ref_name` accepts `name`. */
+export declare function isValidRefName(name: string): boolean;
+
+/**
+ * Last commit that changed each immediate child of `dir` (repo-relative,
+ * empty for the root) as it exists at HEAD, keyed by the child's
+ * repo-relative path: the file browser's "last modified" column.
+ *
+ * History is walked once, newest first, and stops when every child has a
+ * commit or after `max_commits` (default 10000) commits; children not
+ * reached by then are left out. Like `git log -1 -- <path>`, merges only
+ * count if they differ from every parent.
+ */
+export declare function lastCommitsForDir(
+  cwd: string,
+  dir: string,
+  maxCommits?: number | undefined | null,
+): Promise<Record<string, GitLogEntry>>;
+
+/** Branch names as returned by `list_branches_full`. */
 export declare function listBranches(cwd: string): Promise<Array<string>>;
 
 export declare function listBranchesDetailed(cwd: string): Promise<Array<BranchDetailedInfo>>;
 
+/**
+ * Local branches plus `origin` branches without a local counterpart, and
+ * whether HEAD is detached (in which case no branch is checked out and
+ * `detached_hash` holds the commit).
+ */
+export declare function listBranchesFull(cwd: string): Promise<GetBranchesResult>;
+
+/**
+ * List remote-tracking branches (`refs/remotes/<remote>/*`) of `remote`, or
+ * of every remote when `remote` is not given. `HEAD` pseudo-refs are
+ * skipped.
+ */
+export declare function listRemoteBranches(
+  cwd: string,
+  remote?: string | undefined | null,
+): Promise<Array<RemoteBranchInfo>>;
+
+/**
+ * The commits `get_status_summary` counts as unpushed, newest first: those
+ * in `<upstream>..HEAD`, or `<base_branch>..HEAD` when the branch has no
+ * upstream. Empty on a detached HEAD or with neither to compare against.
+ * At most `limit` (default 50) are returned.
+ */
+export declare function listUnpushedCommits(
+  cwd: string,
+  baseBranch?: string | undefined | null,
+  limit?: number | undefined | null,
+): Promise<Array<GitLogEntry>>;
+
+/** A `GitLogEntry` plus its parents, for callers that draw their own graph. */
+export interface LogGraphEntry {
+  hash: string;
+  shortHash: string;
+  author: string;
+  relativeDate: string;
+  message: string;
+  /** Empty for root commits, two or more for merges. */
+  parentHashes: Array<string>;
+}
+
+export interface LogPage {
+  entries: Array<GitLogEntry>;
+  /** Pass back as `cursor` to get the next page; `None` on the last page. */
+  nextCursor?: string;
+}
+
+export interface MergeLine {
+  fromColumn: number;
+  toColumn: number;
+  /** Commit at which the line starts or ends. */
+  commitHash: string;
+}
+
+/**
+ * `name` normalized (surrounding whitespace, leading and repeated slashes
+ * removed) if it's a valid ref name, such as a branch name to pass to
+ * `create_branch`. The error says which rule is violated: no `..`, `@{`,
+ * spaces, control characters or any of `~^:?*[\`, no component starting
+ * with `.` or ending with `.lock`, no trailing `/` or `.`.
+ */
+export declare function normalizeRefName(name: string): string;
+
+export interface OperationState {
+  /**
+   * `"none"`, `"merge"`, `"rebase-merge"`, `"rebase-apply"`,
+   * `"cherry-pick"`, `"revert"` or `"bisect"`.
+   */
+  kind: string;
+  /**
+   * Commit being merged, cherry-picked or reverted, or the one a rebase
+   * stopped at.
+   */
+  targetHash?: string;
+  /** Rebase progress: the 1-based step being applied and the total. */
+  step?: number;
+  totalSteps?: number;
+}
+
+export interface PackStats {
+  packCount: number;
+  looseObjectCount: number;
+  totalPackSizeBytes: bigint;
+  totalLooseSizeBytes: bigint;
+  /** More than 1000 loose objects or more than 20 packs. */
+  needsGc: boolean;
+}
+
+export interface PathAttributes {
+  path: string;
+  /**
+   * Requested attribute name to its state: `"true"` when set, `"false"` when
+   * unset (`-attr`), the value for `attr=value`, or `None` when unspecified.
+   */
+  attributes: Record<string, string | null>;
+}
+
 /** Simple ping function to verify the native module loads correctly. */
 export declare function ping(): string;
 
+export interface RemoteBranchInfo {
+  /** Branch name without the remote prefix (`main` for `origin/main`). */
+  name: string;
+  remote: string;
+  /** Local branch whose upstream is this remote branch, if any. */
+  trackingLocal?: string;
+}
+
+/**
+ * Rename local branch `old_name` to `new_name`, like `git branch -m`: the
+ * ref, its reflog and its `branch.<name>` config (upstream included) move
+ * over, and HEAD follows if the branch is checked out. Fails if `new_name`
+ * is not a valid branch name or already exists.
+ */
+export declare function renameBranch(cwd: string, oldName: string, newName: string): Promise<void>;
+
+export interface ResetOptions {
+  /**
+   * Reset even if tracked files have uncommitted changes. `hard_reset`
+   * discards them.
+   */
+  force?: boolean;
+}
+
 export declare function resetSoft(cwd: string): Promise<void>;
+
+/**
+ * Discard worktree changes to `paths` (repo-relative), like
+ * `git restore [--source=HEAD] -- <paths>`.
+ *
+ * `source` is `"HEAD"` (default) or `"index"` for the staged version. Files
+ * are rewritten with the source's content and mode (smudge filters applied),
+ * and files the source doesn't have are deleted. The index is left as is.
+ * Paths that are neither in the index nor in the source, as well as
+ * directories, submodules and (from the index) unmerged paths, are skipped.
+ * Returns the number of paths restored.
+ */
+export declare function restorePaths(
+  cwd: string,
+  paths: Array<string>,
+  source?: string | undefined | null,
+): Promise<number>;
+
+/**
+ * Write `key = value` to the repository's local config (`.git/config`), like
+ * `git config --local`, adding the section if needed and leaving the rest of
+ * the file as is. An empty `value` unsets the key (see `unset_config`).
+ */
+export declare function setConfig(cwd: string, key: string, value: string): Promise<void>;
+
+/**
+ * Set `branch`'s upstream to `upstream` (a remote-tracking branch such as
+ * `origin/main` or `refs/remotes/fork/feature`), writing
+ * `branch.<branch>.remote` and `branch.<branch>.merge` like
+ * `git branch --set-upstream-to`. `None` clears both.
+ */
+export declare function setUpstream(
+  cwd: string,
+  branch: string,
+  upstream?: string | undefined | null,
+): Promise<void>;
+
+export interface SignatureStatus {
+  isSigned: boolean;
+  /**
+   * `None` when the signature couldn't be checked: the verification tool
+   * isn't installed, the signer's key is unknown, or the format isn't
+   * supported.
+   */
+  isValid?: boolean;
+  /** GPG key fingerprint (or key id) or SSH key fingerprint. */
+  signerKey?: string;
+  /** `"gpg"` or `"ssh"`. */
+  method?: string;
+}
+
+/**
+ * Point the current branch (or HEAD itself when detached) at the commit
+ * `target_rev` resolves to, leaving the index and worktree as they are, so
+ * the difference shows up as staged changes. Fails if tracked files have
+ * uncommitted changes unless `options.force` is set.
+ */
+export declare function softReset(
+  cwd: string,
+  targetRev: string,
+  options?: ResetOptions | undefined | null,
+): Promise<void>;
+
+export interface StatusSummaryOptions {
+  /**
+   * How long a cached summary stays valid, in milliseconds (default 500,
+   * at most 60 s; 0 bypasses the cache).
+   */
+  cacheTtlMs?: number;
+  /**
+   * Git pathspecs (e.g. `packages/client`) limiting the dirty file count
+   * and line counts to matching paths; all files when absent.
+   */
+  pathspecs?: Array<string>;
+  /**
+   * `"none"`, `"leading"`, `"trailing"` or `"all"`: keeps whitespace-only
+   * edits out of the line counts of modified files.
+   */
+  ignoreWhitespace?: string;
+  /**
+   * Line-counting diff algorithm: `"histogram"` (default), `"myers"` or
+   * `"minimal"`.
+   */
+  diffAlgorithm?: string;
+  /**
+   * Also compute `staged_file_count`, an extra pass over the index and the
+   * HEAD tree (default false).
+   */
+  includeStaged?: boolean;
+}
+
+export interface StatusSummaryRequest {
+  worktreeCwd: string;
+  baseBranch?: string;
+  projectCwd?: string;
+  options?: StatusSummaryOptions;
+}
+
+export interface TreeEntry {
+  /** Repo-relative path, including the requested prefix. */
+  path: string;
+  /** `"blob"` (files and symlinks), `"tree"` or `"commit"` (submodules). */
+  kind: string;
+  /** Octal mode as `git ls-tree` prints it, e.g. `100644` or `040000`. */
+  mode: string;
+  hash: string;
+  /** Blob size in bytes; `None` for trees and submodules. */
+  size?: number;
+}
+
+/**
+ * Remove every value of `key` from the repository's local config. Succeeds
+ * if the key wasn't set.
+ */
+export declare function unsetConfig(cwd: string, key: string): Promise<void>;
+
+export interface UntrackedNode {
+  /** Repo-relative path. */
+  path: string;
+  isDir: boolean;
+  /** Empty for files, and for directories at `max_depth`. */
+  children: Array<UntrackedNode>;
+}
+
+export interface UserIdentity {
+  name?: string;
+  email?: string;
+}
+
+/**
+ * Check whether commit `hash` is signed and, if the matching tool (`gpg` or
+ * `ssh-keygen`) is installed, whether the signature verifies. SSH
+ * signatures are checked against the key embedded in the signature, without
+ * consulting an allowed-signers list.
+ */
+export declare function verifyCommitSignature(cwd: string, hash: string): Promise<SignatureStatus>;
+
+/**
+ * Whitespace handling for the file diff functions, after `git diff`'s
+ * `-w` / `--ignore-space-change` / `--ignore-space-at-eol`.
+ */
+export type WhitespaceMode = 'none' | 'allSpace' | 'spaceChange' | 'trailingSpace';
@@ -763,19 +763,73 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding;
+module.exports.applyStash = nativeBinding.applyStash;
+module.exports.batchGetStatusSummary = nativeBinding.batchGetStatusSummary;
+module.exports.changedFilesBetween = nativeBinding.changedFilesBetween;
+module.exports.clearStatusCache = nativeBinding.clearStatusCache;
+module.exports.countConflictMarkers = nativeBinding.countConflictMarkers;
+module.exports.createStash = nativeBinding.createStash;
+module.exports.createTag = nativeBinding.createTag;
+module.exports.deleteTag = nativeBinding.deleteTag;
+module.exports.describe = nativeBinding.describe;
+module.exports.diffRefs = nativeBinding.diffRefs;
+module.exports.discardAllChanges = nativeBinding.discardAllChanges;
+module.exports.discardChanges = nativeBinding.discardChanges;
+module.exports.dropStash = nativeBinding.dropStash;
+module.exports.getBranchGraph = nativeBinding.getBranchGraph;
+module.exports.getCherryPickConflicts = nativeBinding.getCherryPickConflicts;
+module.exports.getCommit = nativeBinding.getCommit;
 module.exports.getCommitBody = nativeBinding.getCommitBody;
 module.exports.getCommitFileDiff = nativeBinding.getCommitFileDiff;
 module.exports.getCommitFiles = nativeBinding.getCommitFiles;
+module.exports.getCommitGraphPresence = nativeBinding.getCommitGraphPresence;
+module.exports.getCommitStats = nativeBinding.getCommitStats;
+module.exports.getConfig = nativeBinding.getConfig;
+module.exports.getConfigUser = nativeBinding.getConfigUser;
+module.exports.getContributors = nativeBinding.getContributors;
 module.exports.getCurrentBranch = nativeBinding.getCurrentBranch;
 module.exports.getDefaultBranch = nativeBinding.getDefaultBranch;
+module.exports.getDiffBetweenCommits = nativeBinding.getDiffBetweenCommits;
 module.exports.getDiffSummary = nativeBinding.getDiffSummary;
+module.exports.getFileContributors = nativeBinding.getFileContributors;
+module.exports.getFileTree = nativeBinding.getFileTree;
 module.exports.getFullContextFileDiff = nativeBinding.getFullContextFileDiff;
+module.exports.getGitattributes = nativeBinding.getGitattributes;
+module.exports.getHeadInfo = nativeBinding.getHeadInfo;
+module.exports.getHooks = nativeBinding.getHooks;
+module.exports.getIndexToHeadDiff = nativeBinding.getIndexToHeadDiff;
 module.exports.getLog = nativeBinding.getLog;
+module.exports.getLogGraph = nativeBinding.getLogGraph;
+module.exports.getLogPage = nativeBinding.getLogPage;
+module.exports.getObjectSize = nativeBinding.getObjectSize;
+module.exports.getOperationState = nativeBinding.getOperationState;
 module.exports.getRemoteUrl = nativeBinding.getRemoteUrl;
+module.exports.getRepositoryPackStats = nativeBinding.getRepositoryPackStats;
+module.exports.getSignature = nativeBinding.getSignature;
 module.exports.getSingleFileDiff = nativeBinding.getSingleFileDiff;
+module.exports.getStagedDiffStats = nativeBinding.getStagedDiffStats;
+module.exports.getStashDiff = nativeBinding.getStashDiff;
 module.exports.getStatusSummary = nativeBinding.getStatusSummary;
+module.exports.getTagCommit = nativeBinding.getTagCommit;
 module.exports.getUnpushedHashes = nativeBinding.getUnpushedHashes;
+module.exports.getUntrackedTree = nativeBinding.getUntrackedTree;
+module.exports.hardReset = nativeBinding.hardReset;
+module.exports.isBinaryBlob = nativeBinding.isBinaryBlob;
+module.exports.isDirty = nativeBinding.isDirty;
+module.exports.isValidRefName = nativeBinding.isValidRefName;
+module.exports.lastCommitsForDir = nativeBinding.lastCommitsForDir;
 module.exports.listBranches = nativeBinding.listBranches;
 module.exports.listBranchesDetailed = nativeBinding.listBranchesDetailed;
+module.exports.listBranchesFull = nativeBinding.listBranchesFull;
+module.exports.listRemoteBranches = nativeBinding.listRemoteBranches;
+module.exports.listUnpushedCommits = nativeBinding.listUnpushedCommits;
+module.exports.normalizeRefName = nativeBinding.normalizeRefName;
 module.exports.ping = nativeBinding.ping;
+module.exports.renameBranch = nativeBinding.renameBranch;
 module.exports.resetSoft = nativeBinding.resetSoft;
+module.exports.restorePaths = nativeBinding.restorePaths;
+module.exports.setConfig = nativeBinding.setConfig;
+module.exports.setUpstream = nativeBinding.setUpstream;
+module.exports.softReset = nativeBinding.softReset;
+module.exports.unsetConfig = nativeBinding.unsetConfig;
+module.exports.verifyCommitSignature = nativeBinding.verifyCommitSignature;
@@ -0,0 +1,67 @@
+use gix::bstr::ByteSlice;
+use gix::worktree::stack::state::attributes::Source as AttrSource;
+
+/// `.gitattributes` consulted when deciding whether a file's lines are worth
+/// counting.
+const COUNTING_ATTRIBUTES: [&str; 3] = ["binary", "diff", "filter"];
+
+/// Line-counting relevant attributes of a path.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PathAttrs {
+  /// `binary` is Set or `diff` is Unset (i.e. `-diff`).
+  pub(crate) binary: bool,
+  /// `filter=lfs`: the content is a Git LFS pointer or, once smudged, the
+  /// real (usually large, binary) object — either way not meaningful lines.
+  pub(crate) lfs: bool,
+}
+
+impl PathAttrs {
+  /// True if the file should contribute zero lines to churn counts.
+  pub(crate) fn skip_line_count(self) -> bool {
+    self.binary || self.lfs
+  }
+}
+
+/// Attribute stack and match outcome for `PathAttrs` lookups, or `None` if
+/// the attributes can't be loaded (callers then treat every path as text).
+pub(crate) fn counting_attributes(
+  repo: &gix::Repository,
+  index: &gix::index::File,
+) -> Option<(gix::worktree::Stack, gix::attrs::search::Outcome)> {
+  let attr_handle = repo
+    .attributes_only(index, AttrSource::WorktreeThenIdMapping)
+    .ok()?;
+  let outcome = attr_handle.selected_attribute_matches(COUNTING_ATTRIBUTES);
+  Some((attr_handle.detach(), outcome))
+}
+
+/// Look up the line-counting attributes for `rel_path`.
+pub(crate) fn path_attrs(
+  attr_stack: &mut gix::worktree::Stack,
+  outcome: &mut gix::attrs::search::Outcome,
+  rel_path: &str,
+  objects: &dyn gix::objs::Find,
+) -> PathAttrs {
+  let mut attrs = PathAttrs::default();
+  let platform = match attr_stack.at_entry(rel_path.as_bytes().as_bstr(), None, objects) {
+    Ok(p) => p,
+    Err(_) => return attrs,
+  };
+
+  if !platform.matching_attributes(outcome) {
+    return attrs;
+  }
+
+  for m in outcome.iter() {
+    match (m.assignment.name.as_str(), &m.assignment.state) {
+      ("binary", gix::attrs::StateRef::Set) => attrs.binary = true,
+      ("diff", gix::attrs::StateRef::Unset) => attrs.binary = true,
+      ("filter", gix::attrs::StateRef::Value(value)) if value.as_bstr() == "lfs" => {
+        attrs.lfs = true
+      }
+      _ => {}
+    }
+  }
+
+  attrs
+}
@@ -1,18 +1,82 @@
-use crate::repo_cache::with_repo;
+use crate::git_cli::run_git;
+use crate::repo_cache::{evict_repo, with_repo};
+use crate::status_cache::invalidate_status;
 
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HeadInfo {
+  /// Checked-out branch, `None` when HEAD is detached.
+  pub branch: Option<String>,
+  pub commit_hash: String,
+  pub is_detached: bool,
+}
+
+fn head_info(repo: &gix::Repository) -> napi::Result<HeadInfo> {
+  let head = repo
+    .head()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD: {e}")))?;
+  let commit_hash = repo
+    .head_commit()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to resolve HEAD to a commit: {e}")))?
+    .id
+    .to_string();
+
+  Ok(HeadInfo {
+    branch: head.referent_name().map(|name| name.shorten().to_string()),
+    commit_hash,
+    is_detached: head.is_detached(),
+  })
+}
+
+/// Name of the checked-out branch. On a detached HEAD this falls back to the
+/// short hash of the commit HEAD points at; prefer `get_head_info`, which
+/// tells the two apart. `None` only before the first commit.
 #[napi]
 pub async fn get_current_branch(cwd: String) -> napi::Result<Option<String>> {
   with_repo(&cwd, |repo| {
     let head_ref = repo
       .head_ref()
       .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD: {e}")))?;
+    if let Some(head_ref) = head_ref {
+      return Ok(Some(head_ref.name().shorten().to_string()));
+    }
 
-    Ok(head_ref.map(|r| r.name().shorten().to_string()))
+    let detached_id = repo
+      .head()
+      .ok()
+      .filter(|head| head.is_detached())
+      .and_then(|head| head.id());
+    Ok(detached_id.map(|id| id.shorten_or_id().to_string()))
   })
 }
 
+/// The checked-out branch (if any) and the commit HEAD resolves to. Fails
+/// before the first commit, when HEAD doesn't point at a commit yet.
+#[napi]
+pub async fn get_head_info(cwd: String) -> napi::Result<HeadInfo> {
+  with_repo(&cwd, head_info)
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GetBranchesResult {
+  pub branches: Vec<String>,
+  pub is_detached: bool,
+  /// Full hash of the commit HEAD points at when detached.
+  pub detached_hash: Option<String>,
+}
+
+/// Branch names as returned by `list_branches_full`.
 #[napi]
 pub async fn list_branches(cwd: String) -> napi::Result<Vec<String>> {
+  list_branches_full(cwd).await.map(|result| result.branches)
+}
+
+/// Local branches plus `origin` branches without a local counterpart, and
+/// whether HEAD is detached (in which case no branch is checked out and
+/// `detached_hash` holds the commit).
+#[napi]
+pub async fn list_branches_full(cwd: String) -> napi::Result<GetBranchesResult> {
   with_repo(&cwd, |repo| {
     let refs = repo
       .references()
@@ -57,6 +121,14 @@ pub async fn list_branches(cwd: String) -> napi::Result<Vec<String>> {
       }
     }
 
+    // A detached HEAD is a commit, not a branch, so it's reported separately.
+    let detached_hash = repo
+      .head()
+      .ok()
+      .filter(|head| head.is_detached())
+      .and_then(|head| head.id())
+      .map(|id| id.to_string());
+
     if branches.is_empty() {
       // Fall back to symbolic ref for empty repos
       if let Ok(Some(head_ref)) = repo.head_ref() {
@@ -64,7 +136,11 @@ pub async fn list_branches(cwd: String) -> napi::Result<Vec<String>> {
       }
     }
 
-    Ok(branches)
+    Ok(GetBranchesResult {
+      branches,
+      is_detached: detached_hash.is_some(),
+      detached_hash,
+    })
   })
 }
 
@@ -198,3 +274,186 @@ pub async fn list_branches_detailed(cwd: String) -> napi::Result<Vec<BranchDetai
     Ok(branches)
   })
 }
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RemoteBranchInfo {
+  /// Branch name without the remote prefix (`main` for `origin/main`).
+  pub name: String,
+  pub remote: String,
+  /// Local branch whose upstream is this remote branch, if any.
+  pub tracking_local: Option<String>,
+}
+
+/// List remote-tracking branches (`refs/remotes/<remote>/*`) of `remote`, or
+/// of every remote when `remote` is not given. `HEAD` pseudo-refs are
+/// skipped.
+#[napi]
+pub async fn list_remote_branches(
+  cwd: String,
+  remote: Option<String>,
+) -> napi::Result<Vec<RemoteBranchInfo>> {
+  with_repo(&cwd, |repo| {
+    // Remote-tracking ref name -> local branch that has it as upstream
+    let mut tracked_by: std::collections::HashMap<String, String> =
+      std::collections::HashMap::new();
+    let refs = repo
+      .references()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get references: {e}")))?;
+    let local_refs = refs
+      .local_branches()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to list local branches: {e}")))?;
+    for r in local_refs.flatten() {
+      if let Some(Ok(tracking)) =
+        repo.branch_remote_tracking_ref_name(r.name(), gix::remote::Direction::Fetch)
+      {
+        tracked_by
+          .entry(tracking.as_bstr().to_string())
+          .or_insert_with(|| r.name().shorten().to_string());
+      }
+    }
+
+    // Longest first, so a remote named `team/origin` wins over `team`.
+    let mut remote_names: Vec<String> = repo
+      .remote_names()
+      .iter()
+      .map(|name| name.to_string())
+      .collect();
+    remote_names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+
+    let refs2 = repo
+      .references()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get references: {e}")))?;
+    let remote_refs = refs2
+      .remote_branches()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to list remote branches: {e}")))?;
+
+    let mut branches: Vec<RemoteBranchInfo> = Vec::new();
+    for r in remote_refs.flatten() {
+      let short = r.name().shorten().to_string();
+      // Refs left behind by a removed remote still split at the first `/`.
+      let remote_name = remote_names
+        .iter()
+        .find(|name| short.starts_with(&format!("{name}/")))
+        .cloned()
+        .or_else(|| short.split_once('/').map(|(name, _)| name.to_string()));
+      let Some(remote_name) = remote_name else {
+        continue;
+      };
+      if remote.as_ref().is_some_and(|wanted| *wanted != remote_name) {
+        continue;
+      }
+      let name = short[remote_name.len() + 1..].to_string();
+      if name.is_empty() || name == "HEAD" {
+        continue;
+      }
+      branches.push(RemoteBranchInfo {
+        tracking_local: tracked_by.get(&r.name().as_bstr().to_string()).cloned(),
+        name,
+        remote: remote_name,
+      });
+    }
+
+    branches.sort_by(|a, b| (&a.remote, &a.name).cmp(&(&b.remote, &b.name)));
+    Ok(branches)
+  })
+}
+
+/// Rename local branch `old_name` to `new_name`, like `git branch -m`: the
+/// ref, its reflog and its `branch.<name>` config (upstream included) move
+/// over, and HEAD follows if the branch is checked out. Fails if `new_name`
+/// is not a valid branch name or already exists.
+#[napi]
+pub async fn rename_branch(cwd: String, old_name: String, new_name: String) -> napi::Result<()> {
+  let checked = with_repo(&cwd, |repo| {
+    let new_ref = gix::refs::FullName::try_from(format!("refs/heads/{new_name}"))
+      .map_err(|e| napi::Error::from_reason(format!("Invalid branch name '{new_name}': {e}")))?;
+    repo
+      .find_reference(format!("refs/heads/{old_name}").as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to find branch {old_name}: {e}")))?;
+    if repo
+      .try_find_reference(new_ref.as_bstr())
+      .ok()
+      .flatten()
+      .is_some()
+    {
+      return Err(napi::Error::from_reason(format!(
+        "Branch {new_name} already exists"
+      )));
+    }
+    Ok(())
+  });
+  checked?;
+
+  // gix can move the ref but not its reflog or config section, so the
+  // rename itself is left to git.
+  let result = run_git(&cwd, &["branch", "-m", "--", &old_name, &new_name]).map(|_| ());
+
+  // Evict after write regardless of success/failure to ensure fresh state
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Set `branch`'s upstream to `upstream` (a remote-tracking branch such as
+/// `origin/main` or `refs/remotes/fork/feature`), writing
+/// `branch.<branch>.remote` and `branch.<branch>.merge` like
+/// `git branch --set-upstream-to`. `None` clears both.
+#[napi]
+pub async fn set_upstream(
+  cwd: String,
+  branch: String,
+  upstream: Option<String>,
+) -> napi::Result<()> {
+  let result = match upstream {
+    Some(upstream) => {
+      let set_upstream_to = format!("--set-upstream-to={upstream}");
+      run_git(&cwd, &["branch", &set_upstream_to, "--", &branch])
+    }
+    None => run_git(&cwd, &["branch", "--unset-upstream", "--", &branch]),
+  }
+  .map(|_| ());
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::head_info;
+  use crate::test_util::{git, TempDir};
+
+  /// A repo on branch `main` with two commits.
+  fn repo_with_two_commits(name: &str) -> TempDir {
+    let dir = TempDir::repo(name);
+    git(&dir, &["commit", "-q", "--allow-empty", "-m", "first"]);
+    git(&dir, &["commit", "-q", "--allow-empty", "-m", "second"]);
+    dir
+  }
+
+  #[test]
+  fn head_info_on_branch() {
+    let dir = repo_with_two_commits("head-info-branch");
+    let head = git(&dir, &["rev-parse", "HEAD"]);
+
+    let info = head_info(&gix::open(&*dir).unwrap()).unwrap();
+    assert_eq!(info.branch.as_deref(), Some("main"));
+    assert_eq!(info.commit_hash, head);
+    assert!(!info.is_detached);
+  }
+
+  #[test]
+  fn head_info_on_detached_head() {
+    let dir = repo_with_two_commits("head-info-detached");
+    let first = git(&dir, &["rev-parse", "HEAD~1"]);
+    git(&dir, &["checkout", "-q", &first]);
+
+    let info = head_info(&gix::open(&*dir).unwrap()).unwrap();
+    assert_eq!(info.branch, None);
+    assert_eq!(info.commit_hash, first);
+    assert!(info.is_detached);
+  }
+}
@@ -0,0 +1,319 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use gix::bstr::{BString, ByteSlice};
+use gix::index::entry::Stage;
+use gix::object::tree::EntryKind;
+
+use crate::diff_summary::worktree_blob;
+use crate::restore::checkout_blob;
+
+fn index_error(e: impl std::fmt::Display) -> napi::Error {
+  napi::Error::from_reason(format!("Failed to open index: {e}"))
+}
+
+/// The index of `repo`, or an empty one if it has none yet.
+pub(crate) fn current_index(repo: &gix::Repository) -> napi::Result<gix::index::File> {
+  let index = repo.index_or_empty().map_err(index_error)?;
+  Ok(gix::index::File::clone(&index))
+}
+
+/// Whether `index` has entries left unmerged by a conflict.
+pub(crate) fn has_unmerged(index: &gix::index::State) -> bool {
+  index
+    .entries()
+    .iter()
+    .any(|entry| entry.stage() != Stage::Unconflicted)
+}
+
+/// Tracked files whose worktree state differs from the index: modified,
+/// deleted, type-changed or unmerged.
+pub(crate) fn unstaged_paths(repo: &gix::Repository) -> napi::Result<BTreeSet<String>> {
+  let status_iter = repo
+    .status(gix::progress::Discard)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to create status: {e}")))?
+    .untracked_files(gix::status::UntrackedFiles::None)
+    .into_index_worktree_iter(Vec::<BString>::new())
+    .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
+
+  let mut paths = BTreeSet::new();
+  for entry in status_iter {
+    let entry =
+      entry.map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
+    if let gix::status::index_worktree::Item::Modification { rela_path, .. } = &entry {
+      paths.insert(rela_path.to_string());
+    }
+  }
+  Ok(paths)
+}
+
+/// Tree holding the staged (stage 0) entries of `index`, like
+/// `git write-tree`. Intent-to-add entries have no content yet and are left
+/// out.
+pub(crate) fn write_index_tree(
+  repo: &gix::Repository,
+  index: &gix::index::State,
+) -> napi::Result<gix::ObjectId> {
+  let err = |e: &dyn std::fmt::Display| {
+    napi::Error::from_reason(format!("Failed to write index tree: {e}"))
+  };
+  let mut editor = repo
+    .edit_tree(gix::ObjectId::empty_tree(repo.object_hash()))
+    .map_err(|e| err(&e))?;
+  for entry in index.entries() {
+    if entry.stage() != Stage::Unconflicted
+      || entry
+        .flags
+        .contains(gix::index::entry::Flags::INTENT_TO_ADD)
+    {
+      continue;
+    }
+    let Some(mode) = entry.mode.to_tree_entry_mode() else {
+      continue;
+    };
+    editor
+      .upsert(entry.path(index), mode.kind(), entry.id)
+      .map_err(|e| err(&e))?;
+  }
+  Ok(editor.write().map_err(|e| err(&e))?.detach())
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata, _index_kind: Option<EntryKind>) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  metadata.permissions().mode() & 0o111 != 0
+}
+
+/// Without an executable bit on disk the index entry's mode is kept, as git
+/// does with `core.fileMode=false`.
+#[cfg(not(unix))]
+fn is_executable(_metadata: &std::fs::Metadata, index_kind: Option<EntryKind>) -> bool {
+  index_kind == Some(EntryKind::BlobExecutable)
+}
+
+/// `base_tree` with `paths` replaced by their worktree content (clean
+/// filters applied), or removed where the file no longer exists. Blobs are
+/// written to the object database.
+pub(crate) fn write_worktree_tree(
+  repo: &gix::Repository,
+  index: &gix::index::State,
+  base_tree: gix::ObjectId,
+  paths: &BTreeSet<String>,
+) -> napi::Result<gix::ObjectId> {
+  let workdir = repo.workdir().ok_or_else(|| {
+    napi::Error::from_reason("Cannot read the worktree of a bare repository".to_string())
+  })?;
+  let err = |e: &dyn std::fmt::Display| {
+    napi::Error::from_reason(format!("Failed to write worktree tree: {e}"))
+  };
+  let (mut pipeline, _) = repo
+    .filter_pipeline(None)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to set up filters: {e}")))?;
+  let mut editor = repo.edit_tree(base_tree).map_err(|e| err(&e))?;
+  for path in paths {
+    let index_kind = index
+      .entry_by_path(path.as_bytes().as_bstr())
+      .and_then(|entry| entry.mode.to_tree_entry_mode())
+      .map(|mode| mode.kind());
+    // Submodules are recorded by their index entry, not their contents.
+    if index_kind == Some(EntryKind::Commit) {
+      continue;
+    }
+    let Ok(metadata) = workdir.join(path).symlink_metadata() else {
+      editor.remove(path).map_err(|e| err(&e))?;
+      continue;
+    };
+    let kind = if metadata.is_symlink() {
+      EntryKind::Link
+    } else if is_executable(&metadata, index_kind) {
+      EntryKind::BlobExecutable
+    } else {
+      EntryKind::Blob
+    };
+    let content = worktree_blob(&mut pipeline, index, workdir, path)?;
+    let id = repo.write_blob(content).map_err(|e| err(&e))?;
+    editor.upsert(path, kind, id).map_err(|e| err(&e))?;
+  }
+  Ok(editor.write().map_err(|e| err(&e))?.detach())
+}
+
+/// Blob entries (files and symlinks) of `tree` by path.
+fn tree_blobs(
+  repo: &gix::Repository,
+  tree: gix::ObjectId,
+) -> napi::Result<HashMap<String, (gix::ObjectId, EntryKind)>> {
+  let mut recorder = gix::traverse::tree::Recorder::default();
+  repo
+    .find_tree(tree)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to find tree: {e}")))?
+    .traverse()
+    .breadthfirst(&mut recorder)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to traverse tree: {e}")))?;
+  Ok(
+    recorder
+      .records
+      .into_iter()
+      .filter(|record| !record.mode.is_tree())
+      .map(|record| {
+        (
+          record.filepath.to_string(),
+          (record.oid, record.mode.kind()),
+        )
+      })
+      .collect(),
+  )
+}
+
+/// Paths whose blob or mode differs between trees `from` and `to`, or that
+/// only one of them has.
+pub(crate) fn changed_paths(
+  repo: &gix::Repository,
+  from: gix::ObjectId,
+  to: gix::ObjectId,
+) -> napi::Result<BTreeSet<String>> {
+  let from = tree_blobs(repo, from)?;
+  let to = tree_blobs(repo, to)?;
+  let mut paths: BTreeSet<String> = from
+    .iter()
+    .filter(|(path, entry)| to.get(*path) != Some(entry))
+    .map(|(path, _)| path.clone())
+    .collect();
+  paths.extend(to.keys().filter(|path| !from.contains_key(*path)).cloned());
+  Ok(paths)
+}
+
+/// Paths of all blob entries in `tree`.
+pub(crate) fn tree_paths(
+  repo: &gix::Repository,
+  tree: gix::ObjectId,
+) -> napi::Result<BTreeSet<String>> {
+  Ok(tree_blobs(repo, tree)?.into_keys().collect())
+}
+
+/// Delete the worktree file at `path`, then any parent directories that
+/// are left empty.
+pub(crate) fn remove_worktree_file(workdir: &Path, path: &str) -> napi::Result<()> {
+  let dest = workdir.join(path);
+  match dest.symlink_metadata() {
+    Ok(metadata) if !metadata.is_dir() => std::fs::remove_file(&dest)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to delete {path}: {e}")))?,
+    _ => return Ok(()),
+  }
+  let mut dir = dest.parent();
+  while let Some(parent) = dir {
+    if parent == workdir || std::fs::remove_dir(parent).is_err() {
+      break;
+    }
+    dir = parent.parent();
+  }
+  Ok(())
+}
+
+/// Make `paths` in the worktree match `tree`: files are written with the
+/// tree's content and mode, and deleted where the tree doesn't have them.
+/// Submodules are skipped. Returns the blob written for each path.
+pub(crate) fn write_paths(
+  repo: &gix::Repository,
+  tree: gix::ObjectId,
+  paths: &BTreeSet<String>,
+) -> napi::Result<HashMap<String, gix::ObjectId>> {
+  let workdir = repo.workdir().ok_or_else(|| {
+    napi::Error::from_reason("Cannot check out files in a bare repository".to_string())
+  })?;
+  let tree = repo
+    .find_tree(tree)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to find tree: {e}")))?;
+  let (mut pipeline, _) = repo
+    .filter_pipeline(None)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to set up filters: {e}")))?;
+
+  let mut written = HashMap::new();
+  for path in paths {
+    let entry = tree
+      .lookup_entry_by_path(path)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to look up {path}: {e}")))?;
+    match entry.map(|e| (e.object_id(), e.mode().kind())) {
+      Some((_, EntryKind::Commit)) => {}
+      // A directory replacing a file: its files are among `paths` too.
+      Some((_, EntryKind::Tree)) => remove_worktree_file(workdir, path)?,
+      Some((id, kind)) => {
+        checkout_blob(repo, &mut pipeline, &workdir.join(path), path, id, kind)?;
+        written.insert(path.clone(), id);
+      }
+      None => remove_worktree_file(workdir, path)?,
+    }
+  }
+  Ok(written)
+}
+
+/// Write `index` as the repository's index. Entries unchanged from `old`
+/// keep its file stats, and entries for files just `written` with the
+/// entry's content get fresh ones, so the next status scan doesn't rehash
+/// them. Other entries keep empty stats and are compared by content.
+pub(crate) fn write_index(
+  repo: &gix::Repository,
+  mut index: gix::index::File,
+  old: &gix::index::State,
+  written: &HashMap<String, gix::ObjectId>,
+) -> napi::Result<()> {
+  let workdir = repo.workdir();
+  for (entry, path) in index.entries_mut_with_paths() {
+    if entry.stage() != Stage::Unconflicted {
+      continue;
+    }
+    let path_str = path.to_str_lossy();
+    if let Some(id) = written.get(path_str.as_ref()) {
+      if *id != entry.id {
+        continue;
+      }
+      let stat = workdir
+        .and_then(|dir| gix::index::fs::Metadata::from_path_no_follow(&dir.join(&*path_str)).ok())
+        .and_then(|metadata| gix::index::entry::Stat::from_fs(&metadata).ok());
+      if let Some(stat) = stat {
+        entry.stat = stat;
+      }
+    } else if let Some(previous) = old.entry_by_path_and_stage(path, Stage::Unconflicted) {
+      if previous.id == entry.id && previous.mode == entry.mode {
+        entry.stat = previous.stat;
+      }
+    }
+  }
+  index
+    .write(gix::index::write::Options::default())
+    .map_err(|e| napi::Error::from_reason(format!("Failed to write index: {e}")))
+}
+
+/// Make the index and worktree match `tree`, like `git reset --hard`:
+/// files whose index entry changes or that have unstaged changes are
+/// rewritten, and tracked files `tree` doesn't have are deleted. Untracked
+/// files are left alone.
+pub(crate) fn checkout_tree(repo: &gix::Repository, tree: gix::ObjectId) -> napi::Result<()> {
+  let old = current_index(repo)?;
+  let index = repo
+    .index_from_tree(&tree)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read tree into index: {e}")))?;
+
+  let mut paths = unstaged_paths(repo)?;
+  for entry in old.entries() {
+    let path = entry.path(&old);
+    let unchanged = entry.stage() == Stage::Unconflicted
+      && index
+        .entry_by_path_and_stage(path, Stage::Unconflicted)
+        .is_some_and(|new| new.id == entry.id && new.mode == entry.mode);
+    if !unchanged {
+      paths.insert(path.to_string());
+    }
+  }
+  for entry in index.entries() {
+    let path = entry.path(&index);
+    if old
+      .entry_by_path_and_stage(path, Stage::Unconflicted)
+      .is_none()
+    {
+      paths.insert(path.to_string());
+    }
+  }
+
+  let written = write_paths(repo, tree, &paths)?;
+  write_index(repo, index, &old, &written)
+}
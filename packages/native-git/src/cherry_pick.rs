@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+
+use gix::merge::tree::TreatAsUnresolved;
+
+use crate::repo_cache::with_repo;
+
+/// Paths that would conflict if `commit_hash` were cherry-picked onto HEAD,
+/// or an empty list if it would apply cleanly.
+///
+/// Runs a three-way tree merge of the commit's first parent (base), HEAD
+/// (ours) and the commit (theirs) entirely in memory: nothing is written to
+/// the object database, index or worktree.
+#[napi]
+pub async fn get_cherry_pick_conflicts(
+  cwd: String,
+  commit_hash: String,
+) -> napi::Result<Vec<String>> {
+  with_repo(&cwd, |repo| {
+    // Merged blobs and trees land in memory instead of the object database.
+    let repo = repo.clone().with_object_memory();
+
+    let commit = repo
+      .rev_parse_single(commit_hash.as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Commit not found: {commit_hash} ({e})")))?
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+      .peel_to_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Commit not found: {commit_hash} ({e})")))?;
+    let their_tree = commit
+      .tree_id()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get commit tree: {e}")))?
+      .detach();
+
+    // A root commit is cherry-picked against the empty tree.
+    let base_tree = match commit.parent_ids().next() {
+      Some(parent) => parent
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read parent commit: {e}")))?
+        .peel_to_commit()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read parent commit: {e}")))?
+        .tree_id()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to get parent tree: {e}")))?
+        .detach(),
+      None => gix::ObjectId::empty_tree(repo.object_hash()),
+    };
+
+    let our_tree = repo
+      .head_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?
+      .tree_id()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD tree: {e}")))?
+      .detach();
+
+    let options = repo
+      .tree_merge_options()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read merge options: {e}")))?;
+    let outcome = repo
+      .merge_trees(base_tree, our_tree, their_tree, Default::default(), options)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to merge trees: {e}")))?;
+
+    let how = TreatAsUnresolved::default();
+    let paths: BTreeSet<String> = outcome
+      .conflicts
+      .iter()
+      .filter(|conflict| conflict.is_unresolved(how))
+      .flat_map(|conflict| [conflict.ours.location(), conflict.theirs.location()])
+      .map(|location| location.to_string())
+      .collect();
+
+    Ok(paths.into_iter().collect())
+  })
+}
@@ -0,0 +1,287 @@
+use gix::bstr::ByteSlice;
+
+use crate::attributes::{counting_attributes, path_attrs, PathAttrs};
+use crate::diff_summary::{matches_any_pattern, FileDiffSummaryItem};
+use crate::file_diff::{is_binary, DiffOptions};
+use crate::repo_cache::with_repo;
+use crate::staged_stats::{read_blob, FileDiffStat};
+use crate::status_summary::count_line_changes;
+
+/// Similarity git uses for rename detection by default (`-M50%`).
+const DEFAULT_RENAME_SIMILARITY: u32 = 50;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommitDiffEntry {
+  pub path: String,
+  /// Source path for `renamed` entries.
+  pub previous_path: Option<String>,
+  pub status: String,
+}
+
+pub(crate) fn resolve_tree<'repo>(repo: &'repo gix::Repository, rev: &str) -> napi::Result<gix::Tree<'repo>> {
+  repo
+    .rev_parse_single(rev)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to parse revision: {e}")))?
+    .object()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+    .try_into_commit()
+    .map_err(|e| napi::Error::from_reason(format!("Not a commit: {e}")))?
+    .tree()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get commit tree: {e}")))
+}
+
+/// Rewrite tracking for a similarity threshold in percent: 0 disables rename
+/// detection, 100 only pairs up identical content.
+fn rewrites_for(similarity: u32) -> Option<gix::diff::Rewrites> {
+  if similarity == 0 {
+    return None;
+  }
+  Some(gix::diff::Rewrites {
+    percentage: Some(similarity as f32 / 100.0),
+    ..Default::default()
+  })
+}
+
+fn diff_between_commits(
+  repo: &gix::Repository,
+  from: &str,
+  to: &str,
+  rename_similarity: u32,
+) -> napi::Result<Vec<CommitDiffEntry>> {
+  let old_tree = resolve_tree(repo, from)?;
+  let new_tree = resolve_tree(repo, to)?;
+
+  let options = gix::diff::Options::default().with_rewrites(rewrites_for(rename_similarity));
+  let changes = repo
+    .diff_tree_to_tree(&old_tree, &new_tree, options)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to diff trees: {e}")))?;
+
+  use gix::object::tree::diff::ChangeDetached;
+  let mut entries: Vec<CommitDiffEntry> = Vec::new();
+  for change in changes {
+    let (path, previous_path, status) = match change {
+      ChangeDetached::Addition {
+        location,
+        entry_mode,
+        ..
+      } if !entry_mode.is_tree() => (location, None, "added"),
+      ChangeDetached::Deletion {
+        location,
+        entry_mode,
+        ..
+      } if !entry_mode.is_tree() => (location, None, "deleted"),
+      ChangeDetached::Modification {
+        location,
+        entry_mode,
+        ..
+      } if !entry_mode.is_tree() => (location, None, "modified"),
+      ChangeDetached::Rewrite {
+        source_location,
+        location,
+        entry_mode,
+        copy,
+        ..
+      } if !entry_mode.is_tree() => (
+        location,
+        Some(source_location),
+        if copy { "copied" } else { "renamed" },
+      ),
+      _ => continue,
+    };
+    entries.push(CommitDiffEntry {
+      path: path.to_str_lossy().to_string(),
+      previous_path: previous_path.map(|p| p.to_str_lossy().to_string()),
+      status: status.to_string(),
+    });
+  }
+
+  entries.sort_by(|a, b| a.path.cmp(&b.path));
+  Ok(entries)
+}
+
+/// Files changed between two commits (any revisions that resolve to commits).
+///
+/// Renames are detected at `options.rename_similarity` percent (0-100,
+/// default 50 like git): 0 disables rename detection so renames show up as a
+/// deletion plus an addition, and 100 only pairs files with identical content.
+#[napi]
+pub async fn get_diff_between_commits(
+  cwd: String,
+  from: String,
+  to: String,
+  options: Option<DiffOptions>,
+) -> napi::Result<Vec<CommitDiffEntry>> {
+  let similarity = options
+    .as_ref()
+    .and_then(|o| o.rename_similarity)
+    .unwrap_or(DEFAULT_RENAME_SIMILARITY);
+  if similarity > 100 {
+    return Err(napi::Error::from_reason(format!(
+      "rename_similarity must be between 0 and 100, got {similarity}"
+    )));
+  }
+
+  with_repo(&cwd, |repo| {
+    diff_between_commits(repo, &from, &to, similarity)
+  })
+}
+
+/// Names and statuses of the files changed between two revisions, without
+/// line counts: the cheap counterpart to `diff_refs`. Renames are detected
+/// at git's default 50% similarity and reported as `renamed` under the new
+/// path.
+#[napi]
+pub async fn changed_files_between(
+  cwd: String,
+  from_ref: String,
+  to_ref: String,
+) -> napi::Result<Vec<FileDiffSummaryItem>> {
+  with_repo(&cwd, |repo| {
+    let entries = diff_between_commits(repo, &from_ref, &to_ref, DEFAULT_RENAME_SIMILARITY)?;
+    Ok(
+      entries
+        .into_iter()
+        .map(|entry| FileDiffSummaryItem {
+          path: entry.path,
+          status: entry.status,
+          staged: false,
+        })
+        .collect(),
+    )
+  })
+}
+
+/// Per-file line counts between two revisions, the native analog of
+/// `git diff --numstat <from_ref> <to_ref>` (without rename detection).
+/// Binary and Git LFS files are flagged with zero counts. Paths containing
+/// any of `exclude_patterns` are left out.
+#[napi]
+pub async fn diff_refs(
+  cwd: String,
+  from_ref: String,
+  to_ref: String,
+  exclude_patterns: Option<Vec<String>>,
+) -> napi::Result<Vec<FileDiffStat>> {
+  with_repo(&cwd, |repo| {
+    let exclude = exclude_patterns.unwrap_or_default();
+    let old_tree = resolve_tree(repo, &from_ref)?;
+    let new_tree = resolve_tree(repo, &to_ref)?;
+    let changes = repo
+      .diff_tree_to_tree(&old_tree, &new_tree, gix::diff::Options::default())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to diff trees: {e}")))?;
+
+    let mut counting_attrs = repo
+      .open_index()
+      .ok()
+      .and_then(|index| counting_attributes(repo, &index));
+
+    use gix::object::tree::diff::ChangeDetached;
+    let mut stats: Vec<FileDiffStat> = Vec::new();
+    for change in changes {
+      let (location, old_id, new_id, status) = match change {
+        ChangeDetached::Addition {
+          location,
+          entry_mode,
+          id,
+          ..
+        } if entry_mode.is_blob() => (location, None, Some(id), "added"),
+        ChangeDetached::Deletion {
+          location,
+          entry_mode,
+          id,
+          ..
+        } if entry_mode.is_blob() => (location, Some(id), None, "deleted"),
+        ChangeDetached::Modification {
+          location,
+          previous_id,
+          entry_mode,
+          id,
+          ..
+        } if entry_mode.is_blob() => (location, Some(previous_id), Some(id), "modified"),
+        _ => continue,
+      };
+      let path = location.to_str_lossy().to_string();
+      if !exclude.is_empty() && matches_any_pattern(&path, &exclude) {
+        continue;
+      }
+
+      let attrs = match counting_attrs {
+        Some((ref mut stack, ref mut outcome)) => path_attrs(stack, outcome, &path, &repo.objects),
+        None => PathAttrs::default(),
+      };
+      let old = read_blob(repo, old_id.as_ref());
+      let new = read_blob(repo, new_id.as_ref());
+      let binary = attrs.skip_line_count() || is_binary(&old) || is_binary(&new);
+      let (added, deleted) = if binary {
+        (0, 0)
+      } else {
+        count_line_changes(&old, &new, gix::diff::blob::Algorithm::Histogram)
+      };
+
+      stats.push(FileDiffStat {
+        path,
+        status: status.to_string(),
+        added,
+        deleted,
+        binary,
+        lfs: attrs.lfs,
+      });
+    }
+
+    stats.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(stats)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::diff_between_commits;
+  use crate::test_util::{git, TempDir};
+
+  /// A repo whose HEAD renames `old.txt` to `new.txt` and edits one of its
+  /// twenty lines (~95% similar).
+  fn repo_with_edited_rename(name: &str) -> TempDir {
+    let dir = TempDir::repo(name);
+    let lines: Vec<String> = (1..=20).map(|i| format!("line number {i}")).collect();
+    std::fs::write(dir.join("old.txt"), lines.join("\n") + "\n").unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "initial"]);
+
+    let mut edited = lines.clone();
+    edited[10] = "an edited line".to_string();
+    std::fs::remove_file(dir.join("old.txt")).unwrap();
+    std::fs::write(dir.join("new.txt"), edited.join("\n") + "\n").unwrap();
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "rename and edit"]);
+    dir
+  }
+
+  fn statuses(dir: &Path, similarity: u32) -> Vec<(String, String)> {
+    let repo = gix::open(dir).unwrap();
+    diff_between_commits(&repo, "HEAD~1", "HEAD", similarity)
+      .unwrap()
+      .into_iter()
+      .map(|e| (e.path, e.status))
+      .collect()
+  }
+
+  #[test]
+  fn rename_threshold_controls_rename_detection() {
+    let dir = repo_with_edited_rename("rename-threshold");
+
+    assert_eq!(
+      statuses(&dir, 50),
+      vec![("new.txt".to_string(), "renamed".to_string())]
+    );
+
+    let split = vec![
+      ("new.txt".to_string(), "added".to_string()),
+      ("old.txt".to_string(), "deleted".to_string()),
+    ];
+    assert_eq!(statuses(&dir, 100), split);
+    assert_eq!(statuses(&dir, 0), split);
+  }
+}
@@ -1,5 +1,7 @@
 use gix::bstr::ByteSlice;
 
+use crate::attributes::{counting_attributes, path_attrs, PathAttrs};
+use crate::file_diff::is_binary;
 use crate::repo_cache::with_repo;
 use crate::status_summary::LineCounter;
 
@@ -10,10 +12,21 @@ pub struct CommitFileEntry {
   pub status: String,
   pub additions: u32,
   pub deletions: u32,
+  /// Tracked with Git LFS (`filter=lfs`, per the current `.gitattributes`);
+  /// its lines are not counted.
+  pub lfs: bool,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommitStats {
+  pub files_changed: u32,
+  pub insertions: u32,
+  pub deletions: u32,
 }
 
 /// Recursively walk a tree, building a map of path -> blob OID.
-fn build_tree_map(
+pub(crate) fn build_tree_map(
   repo: &gix::Repository,
   tree: Option<&gix::Tree<'_>>,
 ) -> std::collections::HashMap<String, gix::ObjectId> {
@@ -56,9 +69,8 @@ fn count_lines(data: &[u8]) -> u32 {
   if data.is_empty() {
     return 0;
   }
-  let check = data.len().min(8192);
-  if data[..check].contains(&0) {
-    return 0; // binary
+  if is_binary(data) {
+    return 0;
   }
   let mut n: u32 = 0;
   for &b in data {
@@ -73,14 +85,7 @@ fn count_lines(data: &[u8]) -> u32 {
 }
 
 fn count_diff_lines(old: &[u8], new: &[u8]) -> (u32, u32) {
-  let is_bin = |d: &[u8]| -> bool {
-    if d.is_empty() {
-      return false;
-    }
-    let c = d.len().min(8192);
-    d[..c].contains(&0)
-  };
-  if is_bin(old) || is_bin(new) {
+  if is_binary(old) || is_binary(new) {
     return (0, 0);
   }
 
@@ -96,6 +101,22 @@ pub async fn get_commit_files(cwd: String, hash: String) -> napi::Result<Vec<Com
   })
 }
 
+/// Totals of `get_commit_files`, like the `git show --shortstat` line: the
+/// commit against its first parent (the empty tree for root commits, the
+/// first parent only for merges). Binary and Git LFS files count as changed
+/// files without lines.
+#[napi]
+pub async fn get_commit_stats(cwd: String, hash: String) -> napi::Result<CommitStats> {
+  with_repo(&cwd, |repo| {
+    let files = _get_commit_files_inner(repo, &hash)?;
+    Ok(CommitStats {
+      files_changed: files.len() as u32,
+      insertions: files.iter().map(|f| f.additions).sum(),
+      deletions: files.iter().map(|f| f.deletions).sum(),
+    })
+  })
+}
+
 fn _get_commit_files_inner(repo: &gix::Repository, hash: &str) -> napi::Result<Vec<CommitFileEntry>> {
   let commit_id = repo
     .rev_parse_single(hash)
@@ -130,11 +151,20 @@ fn _get_commit_files_inner(repo: &gix::Repository, hash: &str) -> napi::Result<V
     all_paths.insert(path.clone());
   }
 
+  let mut counting_attrs = repo
+    .open_index()
+    .ok()
+    .and_then(|index| counting_attributes(repo, &index));
+
   let mut files: Vec<CommitFileEntry> = Vec::new();
 
   for path in &all_paths {
     let old_id = parent_entries.get(path);
     let new_id = commit_entries.get(path);
+    let attrs = match counting_attrs {
+      Some((ref mut stack, ref mut outcome)) => path_attrs(stack, outcome, path, &repo.objects),
+      None => PathAttrs::default(),
+    };
 
     match (old_id, new_id) {
       (None, Some(nid)) => {
@@ -144,12 +174,13 @@ fn _get_commit_files_inner(repo: &gix::Repository, hash: &str) -> napi::Result<V
           .ok()
           .map(|o| o.detach().data)
           .unwrap_or_default();
-        let additions = count_lines(&new_blob);
+        let additions = if attrs.skip_line_count() { 0 } else { count_lines(&new_blob) };
         files.push(CommitFileEntry {
           path: path.clone(),
           status: "added".to_string(),
           additions,
           deletions: 0,
+          lfs: attrs.lfs,
         });
       }
       (Some(oid), None) => {
@@ -159,12 +190,13 @@ fn _get_commit_files_inner(repo: &gix::Repository, hash: &str) -> napi::Result<V
           .ok()
           .map(|o| o.detach().data)
           .unwrap_or_default();
-        let deletions = count_lines(&old_blob);
+        let deletions = if attrs.skip_line_count() { 0 } else { count_lines(&old_blob) };
         files.push(CommitFileEntry {
           path: path.clone(),
           status: "deleted".to_string(),
           additions: 0,
           deletions,
+          lfs: attrs.lfs,
         });
       }
       (Some(oid), Some(nid)) if oid != nid => {
@@ -179,12 +211,17 @@ fn _get_commit_files_inner(repo: &gix::Repository, hash: &str) -> napi::Result<V
           .ok()
           .map(|o| o.detach().data)
           .unwrap_or_default();
-        let (additions, deletions) = count_diff_lines(&old_blob, &new_blob);
+        let (additions, deletions) = if attrs.skip_line_count() {
+          (0, 0)
+        } else {
+          count_diff_lines(&old_blob, &new_blob)
+        };
         files.push(CommitFileEntry {
           path: path.clone(),
           status: "modified".to_string(),
           additions,
           deletions,
+          lfs: attrs.lfs,
         });
       }
       _ => {} // Same OID = unchanged
@@ -0,0 +1,134 @@
+use crate::git_cli::run_git;
+use crate::repo_cache::{evict_repo, with_repo};
+use crate::status_cache::invalidate_status;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct UserIdentity {
+  pub name: Option<String>,
+  pub email: Option<String>,
+}
+
+/// Non-empty value of environment variable `key`.
+fn env_value(key: &str) -> Option<String> {
+  std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+/// The author identity git would use for a commit in `cwd`:
+/// `GIT_AUTHOR_NAME` / `GIT_AUTHOR_EMAIL` if set, else `user.name` /
+/// `user.email` from the merged system, global and local config. Each field
+/// is `None` independently when it isn't configured.
+#[napi]
+pub async fn get_config_user(cwd: String) -> napi::Result<UserIdentity> {
+  with_repo(&cwd, |repo| {
+    let config = repo.config_snapshot();
+    let from_config = |key: &str| {
+      config
+        .string(key)
+        .map(|value| value.to_string())
+        .filter(|value| !value.is_empty())
+    };
+
+    Ok(UserIdentity {
+      name: env_value("GIT_AUTHOR_NAME").or_else(|| from_config("user.name")),
+      email: env_value("GIT_AUTHOR_EMAIL").or_else(|| from_config("user.email")),
+    })
+  })
+}
+
+/// Effective value of config `key` (dotted, e.g. `core.autocrlf` or
+/// `branch.main.remote`), with local config overriding global and global
+/// overriding system. `None` if the key isn't set.
+#[napi]
+pub async fn get_config(cwd: String, key: String) -> napi::Result<Option<String>> {
+  with_repo(&cwd, |repo| {
+    Ok(
+      repo
+        .config_snapshot()
+        .string(key.as_str())
+        .map(|value| value.to_string()),
+    )
+  })
+}
+
+/// `user.name` and `user.email` from the merged config. Unlike
+/// `get_config_user`, `GIT_AUTHOR_*` environment variables are ignored.
+#[napi]
+pub async fn get_signature(cwd: String) -> napi::Result<UserIdentity> {
+  with_repo(&cwd, |repo| {
+    let config = repo.config_snapshot();
+    let value = |key: &str| config.string(key).map(|value| value.to_string());
+    Ok(UserIdentity {
+      name: value("user.name"),
+      email: value("user.email"),
+    })
+  })
+}
+
+/// Check that `key` has git's `section.key` or `section.subsection.key`
+/// shape: alphanumeric or `-` section and name (the name starting with a
+/// letter), and no newline anywhere.
+fn validate_config_key(key: &str) -> napi::Result<()> {
+  let invalid = |reason: &str| {
+    Err(napi::Error::from_reason(format!(
+      "Invalid config key '{key}': {reason}"
+    )))
+  };
+  if key.contains(['\n', '\0']) {
+    return invalid("it must not contain newlines");
+  }
+  let (Some((section, _)), Some((_, name))) = (key.split_once('.'), key.rsplit_once('.')) else {
+    return invalid("expected section.key");
+  };
+  let is_config_word = |s: &str| s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+  if section.is_empty() || !is_config_word(section) {
+    return invalid("the section must be alphanumeric");
+  }
+  if !name.starts_with(|c: char| c.is_ascii_alphabetic()) || !is_config_word(name) {
+    return invalid("the name must be alphanumeric and start with a letter");
+  }
+  Ok(())
+}
+
+/// Write `key = value` to the repository's local config (`.git/config`), like
+/// `git config --local`, adding the section if needed and leaving the rest of
+/// the file as is. An empty `value` unsets the key (see `unset_config`).
+#[napi]
+pub async fn set_config(cwd: String, key: String, value: String) -> napi::Result<()> {
+  if value.is_empty() {
+    return unset_config(cwd, key).await;
+  }
+  validate_config_key(&key)?;
+
+  let result = run_git(&cwd, &["config", "--local", "--", &key, &value]).map(|_| ());
+
+  // Evict after write regardless of success/failure to ensure fresh state
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Remove every value of `key` from the repository's local config. Succeeds
+/// if the key wasn't set.
+#[napi]
+pub async fn unset_config(cwd: String, key: String) -> napi::Result<()> {
+  validate_config_key(&key)?;
+
+  let result = run_git(&cwd, &["config", "--local", "--unset-all", "--", &key])
+    .map(|_| ())
+    .or_else(|e| {
+      // `git config --unset` fails when the key is missing; that's fine here.
+      let is_set = run_git(&cwd, &["config", "--local", "--get-all", "--", &key]).is_ok();
+      if is_set {
+        Err(e)
+      } else {
+        Ok(())
+      }
+    });
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use gix::bstr::ByteSlice;
+
+use crate::file_diff::is_binary;
+use crate::repo_cache::with_repo;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ConflictMarkerCount {
+  pub path: String,
+  pub marker_count: u32,
+}
+
+/// True for a git conflict marker line: `<<<<<<< ours`, `=======` or
+/// `>>>>>>> theirs`.
+/// `line` must already be stripped of its `\n` / `\r\n` terminator.
+fn is_conflict_marker(line: &[u8]) -> bool {
+  if line == b"=======" {
+    return true;
+  }
+  (line.starts_with(b"<<<<<<<") || line.starts_with(b">>>>>>>"))
+    && matches!(line.get(7), None | Some(b' '))
+}
+
+/// Count conflict marker lines in a worktree file. Binary files (null bytes
+/// in the first 8KB) and files missing from disk count as zero.
+fn count_markers_in_file(path: &Path) -> u32 {
+  let data = match std::fs::read(path) {
+    Ok(d) => d,
+    Err(_) => return 0,
+  };
+  if is_binary(&data) {
+    return 0;
+  }
+  data.lines().filter(|line| is_conflict_marker(line)).count() as u32
+}
+
+/// For each path still in conflict in the index (entries at stages 1-3),
+/// count the conflict markers left in its worktree file.
+///
+/// A zero count for a path that is still listed means its content has been
+/// resolved but not yet staged with `git add`.
+#[napi]
+pub async fn count_conflict_markers(cwd: String) -> napi::Result<Vec<ConflictMarkerCount>> {
+  let conflicted = with_repo(&cwd, |repo| {
+    let index = repo
+      .open_index()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+
+    let paths: BTreeSet<String> = index
+      .entries()
+      .iter()
+      .filter(|entry| entry.stage_raw() != 0)
+      .map(|entry| entry.path(&index).to_str_lossy().to_string())
+      .collect();
+    Ok(paths)
+  })?;
+
+  let cwd_path = Path::new(&cwd);
+  Ok(
+    conflicted
+      .into_iter()
+      .map(|path| {
+        let marker_count = count_markers_in_file(&cwd_path.join(&path));
+        ConflictMarkerCount { path, marker_count }
+      })
+      .collect(),
+  )
+}
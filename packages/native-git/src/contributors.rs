@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::file_diff::is_binary;
+use crate::repo_cache::with_repo;
+use crate::staged_stats::read_blob;
+use crate::status_summary::count_line_changes;
+
+/// Commits visited at most by `get_contributors`.
+const MAX_CONTRIBUTOR_WALK: usize = 50_000;
+
+/// Commits visited at most by `get_file_contributors`.
+const MAX_FILE_CONTRIBUTOR_WALK: usize = 500;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Contributor {
+  /// Name and email as spelled on the contributor's most recent commit.
+  pub name: String,
+  pub email: String,
+  pub commit_count: u32,
+}
+
+/// Commit authors in HEAD's history, most commits first (ties by name),
+/// grouped by case-insensitive email so different spellings of one person's
+/// name are counted together. With `since_secs` (Unix seconds), only commits
+/// committed at or after that time count. At most the newest 50000 commits
+/// are looked at.
+#[napi]
+pub async fn get_contributors(
+  cwd: String,
+  since_secs: Option<i64>,
+) -> napi::Result<Vec<Contributor>> {
+  with_repo(&cwd, |repo| {
+    let head_commit = match repo.head_commit() {
+      Ok(c) => c,
+      Err(_) => return Ok(Vec::new()),
+    };
+
+    use gix::revision::walk::Sorting;
+    let sorting = match since_secs {
+      Some(seconds) => Sorting::ByCommitTimeCutoff {
+        order: Default::default(),
+        seconds,
+      },
+      None => Sorting::ByCommitTime(Default::default()),
+    };
+    let iter = repo
+      .rev_walk([head_commit.id])
+      .sorting(sorting)
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+
+    let mut by_email: HashMap<String, Contributor> = HashMap::new();
+    for commit_info in iter.take(MAX_CONTRIBUTOR_WALK) {
+      let info =
+        commit_info.map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
+      let commit = info
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+      let Ok(author) = commit.author() else {
+        continue;
+      };
+
+      // Newest first, so the first spelling seen is the most recent one.
+      by_email
+        .entry(author.email.to_string().to_lowercase())
+        .or_insert_with(|| Contributor {
+          name: author.name.to_string(),
+          email: author.email.to_string(),
+          commit_count: 0,
+        })
+        .commit_count += 1;
+    }
+
+    let mut contributors: Vec<Contributor> = by_email.into_values().collect();
+    contributors.sort_by(|a, b| {
+      b.commit_count
+        .cmp(&a.commit_count)
+        .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(contributors)
+  })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ContributorSummary {
+  pub author: String,
+  pub commit_count: u32,
+  pub lines_added: u32,
+  pub lines_deleted: u32,
+  /// Author date of their most recent commit to the file, in seconds since
+  /// the Unix epoch.
+  pub last_commit_timestamp: i64,
+}
+
+/// Blob at `rel_path` in `commit`'s tree, if the file exists there.
+fn blob_at(commit: &gix::Commit<'_>, rel_path: &str) -> Option<gix::ObjectId> {
+  let entry = commit.tree().ok()?.lookup_entry_by_path(rel_path).ok()??;
+  Some(entry.object_id())
+}
+
+/// Authors of the commits in HEAD's history that changed `rel_path`, most
+/// commits first, with the lines each added and deleted (binary versions
+/// count no lines). Authors are grouped by name. A merge counts only if the
+/// file differs from every parent, as in `git log -- <path>`, and its lines
+/// are counted against the first parent. Renames are not followed. At most
+/// the newest 500 commits are looked at.
+#[napi]
+pub async fn get_file_contributors(
+  cwd: String,
+  rel_path: String,
+) -> napi::Result<Vec<ContributorSummary>> {
+  with_repo(&cwd, |repo| {
+    let head_commit = match repo.head_commit() {
+      Ok(c) => c,
+      Err(_) => return Ok(Vec::new()),
+    };
+
+    let iter = repo
+      .rev_walk([head_commit.id])
+      .sorting(gix::revision::walk::Sorting::ByCommitTime(
+        Default::default(),
+      ))
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+
+    let mut by_author: HashMap<String, ContributorSummary> = HashMap::new();
+    for commit_info in iter.take(MAX_FILE_CONTRIBUTOR_WALK) {
+      let info =
+        commit_info.map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
+      let commit = info
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+
+      let current = blob_at(&commit, &rel_path);
+      let parent_blobs: Vec<Option<gix::ObjectId>> = commit
+        .parent_ids()
+        .map(|id| {
+          repo
+            .find_commit(id)
+            .ok()
+            .and_then(|parent| blob_at(&parent, &rel_path))
+        })
+        .collect();
+      let changed = match parent_blobs.first() {
+        Some(_) => parent_blobs.iter().all(|blob| *blob != current),
+        None => current.is_some(),
+      };
+      if !changed {
+        continue;
+      }
+
+      let Ok(author) = commit.author() else {
+        continue;
+      };
+      let old = read_blob(repo, parent_blobs.first().copied().flatten().as_ref());
+      let new = read_blob(repo, current.as_ref());
+      let (added, deleted) = if is_binary(&old) || is_binary(&new) {
+        (0, 0)
+      } else {
+        count_line_changes(&old, &new, gix::diff::blob::Algorithm::Histogram)
+      };
+      let timestamp = author.time().map(|t| t.seconds).unwrap_or(0);
+
+      let name = author.name.to_string();
+      let summary = by_author
+        .entry(name.clone())
+        .or_insert_with(|| ContributorSummary {
+          author: name,
+          commit_count: 0,
+          lines_added: 0,
+          lines_deleted: 0,
+          last_commit_timestamp: timestamp,
+        });
+      summary.commit_count += 1;
+      summary.lines_added += added;
+      summary.lines_deleted += deleted;
+      summary.last_commit_timestamp = summary.last_commit_timestamp.max(timestamp);
+    }
+
+    let mut contributors: Vec<ContributorSummary> = by_author.into_values().collect();
+    contributors.sort_by(|a, b| {
+      b.commit_count
+        .cmp(&a.commit_count)
+        .then_with(|| a.author.cmp(&b.author))
+    });
+    Ok(contributors)
+  })
+}
@@ -1,8 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
 use std::path::Path;
 
 use gix::bstr::{BString, ByteSlice};
+use gix::filter::plumbing::pipeline::convert::ToGitOutcome;
+use gix::worktree::stack::state::attributes::Source as AttrSource;
 
+use crate::commit_diff::resolve_tree;
 use crate::repo_cache::with_repo;
 
 /// True if the nested git repo at `nested_path` has any uncommitted changes.
@@ -35,8 +39,53 @@ pub struct DiffSummaryResult {
   pub truncated: bool,
 }
 
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct DiffSummaryOptions {
+  /// Compare the worktree against this revision's tree instead of reporting
+  /// unstaged and staged changes relative to the index and HEAD.
+  pub base_rev: Option<String>,
+  /// Only report files at or under this repo-relative path, e.g.
+  /// `packages/client`. Taken literally: glob characters match themselves.
+  pub path_prefix: Option<String>,
+}
+
+/// Pathspec patterns for `options.path_prefix` (empty = all files).
+fn prefix_patterns(options: Option<&DiffSummaryOptions>) -> Vec<BString> {
+  options
+    .and_then(|o| o.path_prefix.as_deref())
+    .map(|prefix| prefix.trim_matches('/'))
+    .filter(|prefix| !prefix.is_empty())
+    .map(|prefix| BString::from(format!(":(top,literal){prefix}")))
+    .into_iter()
+    .collect()
+}
+
+/// Matcher for `patterns` to apply to index and tree entries, which the
+/// status iterator doesn't filter for us; `None` when there are none.
+fn entry_pathspec<'repo>(
+  repo: &'repo gix::Repository,
+  index: &gix::index::File,
+  patterns: &[BString],
+) -> napi::Result<Option<gix::Pathspec<'repo>>> {
+  if patterns.is_empty() {
+    return Ok(None);
+  }
+  repo
+    .pathspec(true, patterns, true, index, AttrSource::IdMapping)
+    .map(Some)
+    .map_err(|e| napi::Error::from_reason(format!("Invalid path prefix: {e}")))
+}
+
+fn is_included(pathspec: &mut Option<gix::Pathspec<'_>>, path: &str) -> bool {
+  match pathspec {
+    Some(spec) => spec.is_included(path.as_bytes().as_bstr(), Some(false)),
+    None => true,
+  }
+}
+
 /// Check if a path matches any of the exclude patterns (simple suffix/contains matching).
-fn matches_any_pattern(path: &str, patterns: &[String]) -> bool {
+pub(crate) fn matches_any_pattern(path: &str, patterns: &[String]) -> bool {
   for pat in patterns {
     if path.contains(pat.as_str()) {
       return true;
@@ -45,15 +94,237 @@ fn matches_any_pattern(path: &str, patterns: &[String]) -> bool {
   false
 }
 
+/// Content of the worktree file at `rela_path` as it would be staged: after
+/// clean filters (e.g. `eol` conversion), or the link target for symlinks.
+pub(crate) fn worktree_blob(
+  pipeline: &mut gix::filter::Pipeline<'_>,
+  index: &gix::index::State,
+  workdir: &Path,
+  rela_path: &str,
+) -> napi::Result<Vec<u8>> {
+  let err = |e: &dyn std::fmt::Display| {
+    napi::Error::from_reason(format!("Failed to read {rela_path}: {e}"))
+  };
+  let path = workdir.join(rela_path);
+  let mut content = Vec::new();
+  if path.symlink_metadata().map_err(|e| err(&e))?.is_symlink() {
+    let target = std::fs::read_link(&path).map_err(|e| err(&e))?;
+    content.extend_from_slice(&gix::path::into_bstr(target));
+  } else {
+    let file = std::fs::File::open(&path).map_err(|e| err(&e))?;
+    match pipeline
+      .convert_to_git(file, Path::new(rela_path), index)
+      .map_err(|e| err(&e))?
+    {
+      ToGitOutcome::Unchanged(mut file) => file.read_to_end(&mut content).map(|_| ()),
+      ToGitOutcome::Process(mut read) => read.read_to_end(&mut content).map(|_| ()),
+      ToGitOutcome::Buffer(buf) => {
+        content.extend_from_slice(buf);
+        Ok(())
+      }
+    }
+    .map_err(|e| err(&e))?;
+  }
+  Ok(content)
+}
+
+/// Id of the worktree file at `rela_path` as it would be staged.
+fn worktree_blob_id(
+  repo: &gix::Repository,
+  pipeline: &mut gix::filter::Pipeline<'_>,
+  index: &gix::index::File,
+  workdir: &Path,
+  rela_path: &str,
+) -> napi::Result<gix::ObjectId> {
+  let content = worktree_blob(pipeline, index, workdir, rela_path)?;
+  gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, &content)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read {rela_path}: {e}")))
+}
+
+/// Files that differ between `base_rev`'s tree and the worktree, including
+/// untracked files (as `added`). Tracked files whose worktree content matches
+/// the index are compared by index id, so only modified files are hashed.
+fn diff_base_against_worktree(
+  repo: &gix::Repository,
+  base_rev: &str,
+  patterns: &[BString],
+) -> napi::Result<Vec<FileDiffSummaryItem>> {
+  let workdir = repo.workdir().ok_or_else(|| {
+    napi::Error::from_reason("Cannot diff against the worktree of a bare repository".to_string())
+  })?;
+
+  let mut base_entries: HashMap<String, gix::ObjectId> = HashMap::new();
+  let mut recorder = gix::traverse::tree::Recorder::default();
+  resolve_tree(repo, base_rev)?
+    .traverse()
+    .breadthfirst(&mut recorder)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to traverse base tree: {e}")))?;
+  for record in recorder.records {
+    if !record.mode.is_tree() {
+      base_entries.insert(record.filepath.to_str_lossy().to_string(), record.oid);
+    }
+  }
+
+  let status_iter = repo
+    .status(gix::progress::Discard)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to create status: {e}")))?
+    .untracked_files(gix::status::UntrackedFiles::Files)
+    // Same submodule handling as the HEAD-relative summary below.
+    .index_worktree_submodules(gix::status::Submodule::Given {
+      ignore: gix::submodule::config::Ignore::None,
+      check_dirty: true,
+    })
+    .into_index_worktree_iter(patterns.to_vec())
+    .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
+
+  // Worktree state relative to the index.
+  let mut modified: HashSet<String> = HashSet::new();
+  let mut removed: HashSet<String> = HashSet::new();
+  let mut conflicted: HashSet<String> = HashSet::new();
+  let mut untracked: Vec<String> = Vec::new();
+  for entry in status_iter {
+    let entry =
+      entry.map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
+    use gix_status::index_as_worktree::{Change, EntryStatus};
+    match &entry {
+      gix::status::index_worktree::Item::Modification { rela_path, status, .. } => {
+        let path = rela_path.to_string();
+        match status {
+          EntryStatus::Conflict { .. } => conflicted.insert(path),
+          EntryStatus::Change(Change::Removed) => removed.insert(path),
+          _ => modified.insert(path),
+        };
+      }
+      gix::status::index_worktree::Item::DirectoryContents { entry, .. } => {
+        untracked.push(entry.rela_path.to_string());
+      }
+      gix::status::index_worktree::Item::Rewrite { dirwalk_entry, .. } => {
+        untracked.push(dirwalk_entry.rela_path.to_string());
+      }
+    }
+  }
+
+  let index = repo
+    .open_index()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+  let (mut pipeline, _) = repo
+    .filter_pipeline(None)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to set up filters: {e}")))?;
+  let mut pathspec = entry_pathspec(repo, &index, patterns)?;
+  base_entries.retain(|path, _| is_included(&mut pathspec, path));
+
+  let mut files: Vec<FileDiffSummaryItem> = Vec::new();
+  let mut seen: HashSet<String> = HashSet::new();
+  let mut push = |path: String, status: &str| {
+    files.push(FileDiffSummaryItem {
+      path,
+      status: status.to_string(),
+      staged: false,
+    })
+  };
+
+  for entry in index.entries().iter() {
+    let path = entry.path(&index).to_str_lossy().to_string();
+    if !is_included(&mut pathspec, &path) || !seen.insert(path.clone()) {
+      continue;
+    }
+    let base_id = base_entries.get(&path);
+    if conflicted.contains(&path) {
+      push(path, "conflicted");
+      continue;
+    }
+    if removed.contains(&path) {
+      if base_id.is_some() {
+        push(path, "deleted");
+      }
+      continue;
+    }
+    let Some(base_id) = base_id else {
+      push(path, "added");
+      continue;
+    };
+    let current_id = if modified.contains(&path) && !entry.mode.is_submodule() {
+      worktree_blob_id(repo, &mut pipeline, &index, workdir, &path)?
+    } else {
+      entry.id
+    };
+    if (modified.contains(&path) && entry.mode.is_submodule()) || current_id != *base_id {
+      push(path, "modified");
+    }
+  }
+
+  for path in untracked {
+    seen.insert(path.clone());
+    match base_entries.get(&path) {
+      Some(base_id) => {
+        if worktree_blob_id(repo, &mut pipeline, &index, workdir, &path)? != *base_id {
+          push(path, "modified");
+        }
+      }
+      None => push(path, "added"),
+    }
+  }
+
+  for path in base_entries.keys() {
+    if !seen.contains(path) {
+      push(path.clone(), "deleted");
+    }
+  }
+
+  files.sort_by(|a, b| a.path.cmp(&b.path));
+  Ok(files)
+}
+
+/// Summarize the changed files in the worktree at `cwd`: unstaged changes
+/// and untracked files (`staged: false`), then staged changes relative to
+/// HEAD (`staged: true`).
+///
+/// With `options.base_rev`, the worktree is instead compared file by file
+/// against that revision's tree, e.g. to show everything changed since
+/// branching off `main`, committed or not. Entries are then `added`,
+/// `modified`, `deleted` or `conflicted`, sorted by path, and all
+/// `staged: false`.
+///
+/// With `options.path_prefix`, only files under that path are scanned and
+/// reported.
 #[napi]
 pub async fn get_diff_summary(
   cwd: String,
   exclude_patterns: Option<Vec<String>>,
   max_files: Option<u32>,
+  options: Option<DiffSummaryOptions>,
 ) -> napi::Result<DiffSummaryResult> {
-  with_repo(&cwd, |repo| {
+  compute_diff_summary(&cwd, exclude_patterns, max_files, options)
+}
+
+fn compute_diff_summary(
+  cwd: &str,
+  exclude_patterns: Option<Vec<String>>,
+  max_files: Option<u32>,
+  options: Option<DiffSummaryOptions>,
+) -> napi::Result<DiffSummaryResult> {
+  with_repo(cwd, |repo| {
     let exclude = exclude_patterns.unwrap_or_default();
     let max = max_files.unwrap_or(0) as usize;
+    let patterns = prefix_patterns(options.as_ref());
+
+    if let Some(base_rev) = options.as_ref().and_then(|o| o.base_rev.as_deref()) {
+      let mut all_files = diff_base_against_worktree(repo, base_rev, &patterns)?;
+      if !exclude.is_empty() {
+        all_files.retain(|f| !matches_any_pattern(&f.path, &exclude));
+      }
+
+      let total = all_files.len() as u32;
+      let truncated = max > 0 && all_files.len() > max;
+      if truncated {
+        all_files.truncate(max);
+      }
+      return Ok(DiffSummaryResult {
+        files: all_files,
+        total,
+        truncated,
+      });
+    }
 
     let status_platform = repo
       .status(gix::progress::Discard)
@@ -69,9 +340,8 @@ pub async fn get_diff_summary(
       });
 
     // into_index_worktree_iter takes pathspec patterns (empty = all files)
-    let empty_patterns: Vec<BString> = Vec::new();
     let status_iter = status_platform
-      .into_index_worktree_iter(empty_patterns)
+      .into_index_worktree_iter(patterns.clone())
       .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
 
     let mut all_files: Vec<FileDiffSummaryItem> = Vec::new();
@@ -128,12 +398,13 @@ pub async fn get_diff_summary(
     let index = repo
       .open_index()
       .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+    let mut pathspec = entry_pathspec(repo, &index, &patterns)?;
 
     for entry in index.entries().iter() {
       let path_str = entry.path(&index).to_str_lossy().to_string();
 
       // Skip files already reported as worktree changes
-      if worktree_changed_paths.contains(&path_str) {
+      if worktree_changed_paths.contains(&path_str) || !is_included(&mut pathspec, &path_str) {
         continue;
       }
 
@@ -174,7 +445,7 @@ pub async fn get_diff_summary(
     // been reported yet, and shelling out to `git status` in the nested repo
     // to decide whether to surface them as modified.
     let already_reported: HashSet<String> = all_files.iter().map(|f| f.path.clone()).collect();
-    let cwd_path = Path::new(&cwd);
+    let cwd_path = Path::new(cwd);
     for entry in index.entries().iter() {
       if !entry.mode.is_submodule() {
         continue;
@@ -183,6 +454,9 @@ pub async fn get_diff_summary(
       if worktree_changed_paths.contains(&path_str) || already_reported.contains(&path_str) {
         continue;
       }
+      if !is_included(&mut pathspec, &path_str) {
+        continue;
+      }
       let nested = cwd_path.join(&path_str);
       if !nested.join(".git").exists() {
         continue;
@@ -213,3 +487,305 @@ pub async fn get_diff_summary(
     })
   })
 }
+
+/// A path whose index version differs from HEAD.
+pub(crate) struct StagedChange {
+  pub(crate) path: String,
+  /// `added`, `modified`, `deleted` or `conflicted`.
+  pub(crate) status: &'static str,
+  /// Mode of the index entry, or of the HEAD entry for deletions.
+  pub(crate) mode: gix::index::entry::Mode,
+  /// Blob in HEAD; `None` if added or conflicted.
+  pub(crate) head_id: Option<gix::ObjectId>,
+  /// Blob in the index; `None` if deleted or conflicted.
+  pub(crate) index_id: Option<gix::ObjectId>,
+}
+
+/// Changes staged in `index` relative to the HEAD tree (an empty tree when
+/// there is no commit yet), as `git diff --cached` sees them without rename
+/// detection, sorted by path. gix's tree-to-index diff leaves out unmerged
+/// and intent-to-add entries; unmerged paths are added back as
+/// `conflicted`, intent-to-add ones stay out as in git.
+pub(crate) fn staged_changes<'repo>(
+  repo: &'repo gix::Repository,
+  index: &gix::index::State,
+  mut pathspec: Option<&mut gix::Pathspec<'repo>>,
+) -> napi::Result<Vec<StagedChange>> {
+  let head_tree = repo
+    .head_tree_id()
+    .map(|id| id.detach())
+    .unwrap_or_else(|_| gix::ObjectId::empty_tree(repo.object_hash()));
+
+  let mut changes = Vec::new();
+  repo
+    .tree_index_status(
+      &head_tree,
+      index,
+      pathspec.as_deref_mut(),
+      gix::status::tree_index::TrackRenames::Disabled,
+      |change, _, _| {
+        use gix::diff::index::ChangeRef;
+        let (status, head_id, index_id) = match &change {
+          ChangeRef::Addition { id, .. } => ("added", None, Some(id.clone().into_owned())),
+          ChangeRef::Deletion { id, .. } => ("deleted", Some(id.clone().into_owned()), None),
+          ChangeRef::Modification {
+            previous_id, id, ..
+          } => (
+            "modified",
+            Some(previous_id.clone().into_owned()),
+            Some(id.clone().into_owned()),
+          ),
+          // Rename tracking is disabled.
+          ChangeRef::Rewrite { .. } => return Ok(std::ops::ControlFlow::Continue(())),
+        };
+        changes.push(StagedChange {
+          path: change.location().to_str_lossy().into_owned(),
+          status,
+          mode: change.entry_mode(),
+          head_id,
+          index_id,
+        });
+        Ok::<_, std::convert::Infallible>(std::ops::ControlFlow::Continue(()))
+      },
+    )
+    .map_err(|e| napi::Error::from_reason(format!("Failed to diff HEAD against index: {e}")))?;
+
+  let mut conflicted: BTreeMap<&gix::bstr::BStr, gix::index::entry::Mode> = BTreeMap::new();
+  for entry in index.entries() {
+    let path = entry.path(index);
+    let included = match pathspec.as_deref_mut() {
+      Some(spec) => spec.is_included(path, Some(false)),
+      None => true,
+    };
+    if entry.stage_raw() != 0 && included {
+      conflicted.entry(path).or_insert(entry.mode);
+    }
+  }
+  changes.extend(conflicted.into_iter().map(|(path, mode)| StagedChange {
+    path: path.to_str_lossy().into_owned(),
+    status: "conflicted",
+    mode,
+    head_id: None,
+    index_id: None,
+  }));
+
+  changes.sort_by(|a, b| a.path.cmp(&b.path));
+  Ok(changes)
+}
+
+/// Summarize the staged changes: the HEAD tree compared against the index,
+/// i.e. what the next commit would contain. Complements `get_diff_summary`,
+/// whose entries are mostly index-to-worktree changes. Every item is
+/// `staged: true`; paths with several index entries (unresolved merges) are
+/// reported as `conflicted`.
+#[napi]
+pub async fn get_index_to_head_diff(
+  cwd: String,
+  max_files: Option<u32>,
+) -> napi::Result<DiffSummaryResult> {
+  with_repo(&cwd, |repo| {
+    let max = max_files.unwrap_or(0) as usize;
+    let index = repo
+      .open_index()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+
+    let mut all_files: Vec<FileDiffSummaryItem> = staged_changes(repo, &index, None)?
+      .into_iter()
+      .map(|change| FileDiffSummaryItem {
+        path: change.path,
+        status: change.status.to_string(),
+        staged: true,
+      })
+      .collect();
+
+    let total = all_files.len() as u32;
+    let truncated = max > 0 && all_files.len() > max;
+    if truncated {
+      all_files.truncate(max);
+    }
+
+    Ok(DiffSummaryResult {
+      files: all_files,
+      total,
+      truncated,
+    })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{compute_diff_summary, matches_any_pattern, staged_changes, DiffSummaryOptions};
+  use crate::test_util::{git, TempDir};
+
+  fn pats(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+  }
+
+  #[test]
+  fn empty_pattern_list_matches_nothing() {
+    assert!(!matches_any_pattern("src/main.rs", &[]));
+    assert!(!matches_any_pattern("", &[]));
+  }
+
+  #[test]
+  fn exact_match() {
+    assert!(matches_any_pattern("Cargo.lock", &pats(&["Cargo.lock"])));
+  }
+
+  #[test]
+  fn pattern_is_prefix_of_path() {
+    assert!(matches_any_pattern("node_modules/react/index.js", &pats(&["node_modules"])));
+  }
+
+  #[test]
+  fn pattern_is_suffix_of_path() {
+    assert!(matches_any_pattern("packages/client/bun.lock", &pats(&[".lock"])));
+  }
+
+  #[test]
+  fn pattern_with_path_separators() {
+    assert!(matches_any_pattern("packages/client/dist/app.js", &pats(&["client/dist/"])));
+    assert!(!matches_any_pattern("packages/client/distribution.ts", &pats(&["client/dist/"])));
+  }
+
+  #[test]
+  fn empty_string_pattern_matches_everything() {
+    // `str::contains("")` is always true — callers must not pass empty patterns.
+    assert!(matches_any_pattern("src/lib.rs", &pats(&[""])));
+    assert!(matches_any_pattern("", &pats(&[""])));
+  }
+
+  #[test]
+  fn substring_footgun_matches_unrelated_files() {
+    // Known limitation of `contains` matching: "lock" also hits "clockwork.rs".
+    assert!(matches_any_pattern("src/clockwork.rs", &pats(&["lock"])));
+  }
+
+  #[test]
+  fn no_match_returns_false() {
+    assert!(!matches_any_pattern("src/main.rs", &pats(&["dist", "node_modules"])));
+  }
+
+  #[test]
+  fn any_of_multiple_patterns_matches() {
+    let exclude = pats(&["dist", "node_modules", ".min.js"]);
+    assert!(matches_any_pattern("public/vendor.min.js", &exclude));
+    assert!(matches_any_pattern("dist/index.html", &exclude));
+  }
+
+  #[test]
+  fn matching_is_case_sensitive() {
+    assert!(!matches_any_pattern("README.md", &pats(&["readme"])));
+  }
+
+  #[test]
+  fn empty_path_only_matches_empty_pattern() {
+    assert!(!matches_any_pattern("", &pats(&["src"])));
+  }
+
+  #[test]
+  fn backslash_separators_are_not_normalized() {
+    assert!(!matches_any_pattern("src\\lib.rs", &pats(&["src/"])));
+  }
+
+  #[test]
+  fn path_prefix_limits_results_to_subdirectory() {
+    let root = TempDir::repo("diff-prefix");
+    for dir in ["packages/a", "packages/b", "packages/ab"] {
+      std::fs::create_dir_all(root.join(dir)).unwrap();
+      std::fs::write(root.join(dir).join("index.ts"), "one\n").unwrap();
+    }
+    git(&root, &["add", "."]);
+    git(&root, &["commit", "-q", "-m", "initial"]);
+
+    for dir in ["packages/a", "packages/b", "packages/ab"] {
+      std::fs::write(root.join(dir).join("index.ts"), "two\n").unwrap();
+      std::fs::write(root.join(dir).join("new.ts"), "new\n").unwrap();
+      std::fs::write(root.join(dir).join("staged.ts"), "staged\n").unwrap();
+    }
+    git(
+      &root,
+      &["add", "packages/a/staged.ts", "packages/b/staged.ts"],
+    );
+
+    let cwd = root.cwd();
+    for base_rev in [None, Some("HEAD".to_string())] {
+      let options = DiffSummaryOptions {
+        base_rev,
+        path_prefix: Some("packages/a".to_string()),
+      };
+      let result = compute_diff_summary(cwd, None, None, Some(options)).unwrap();
+      let mut paths: Vec<&str> = result.files.iter().map(|f| f.path.as_str()).collect();
+      paths.sort();
+      assert_eq!(
+        paths,
+        [
+          "packages/a/index.ts",
+          "packages/a/new.ts",
+          "packages/a/staged.ts"
+        ]
+      );
+    }
+  }
+
+  fn staged(dir: &std::path::Path) -> Vec<(String, &'static str)> {
+    let repo = gix::open(dir).unwrap();
+    let index = repo.open_index().unwrap();
+    staged_changes(&repo, &index, None)
+      .unwrap()
+      .into_iter()
+      .map(|change| (change.path, change.status))
+      .collect()
+  }
+
+  #[test]
+  fn staged_changes_match_git_diff_cached() {
+    let dir = TempDir::repo("staged-changes");
+    for name in ["keep.txt", "edit.txt", "gone.txt"] {
+      std::fs::write(dir.join(name), format!("{name}\n")).unwrap();
+    }
+    git(&dir, &["add", "."]);
+    // No commit yet: everything staged is added.
+    assert_eq!(staged(&dir).len(), 3);
+    git(&dir, &["commit", "-q", "-m", "initial"]);
+
+    std::fs::write(dir.join("edit.txt"), "edited\n").unwrap();
+    std::fs::write(dir.join("new.txt"), "new\n").unwrap();
+    std::fs::write(dir.join("later.txt"), "later\n").unwrap();
+    git(&dir, &["add", "edit.txt", "new.txt"]);
+    git(&dir, &["add", "-N", "later.txt"]);
+    git(&dir, &["rm", "-q", "gone.txt"]);
+
+    let expected = git(&dir, &["diff", "--cached", "--name-status"]);
+    let actual: Vec<String> = staged(&dir)
+      .into_iter()
+      .map(|(path, status)| format!("{}\t{path}", status[..1].to_uppercase()))
+      .collect();
+    assert_eq!(actual.join("\n"), expected);
+  }
+
+  #[test]
+  fn staged_changes_report_unmerged_paths_once() {
+    let dir = TempDir::repo("staged-conflict");
+    std::fs::write(dir.join("file.txt"), "base\n").unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "base"]);
+    git(&dir, &["checkout", "-q", "-b", "other"]);
+    std::fs::write(dir.join("file.txt"), "other\n").unwrap();
+    git(&dir, &["commit", "-q", "-am", "other"]);
+    git(&dir, &["checkout", "-q", "main"]);
+    std::fs::write(dir.join("file.txt"), "main\n").unwrap();
+    git(&dir, &["commit", "-q", "-am", "main"]);
+    // The merge fails with a conflict, so it runs outside `git()`.
+    git(&dir, &["config", "user.name", "Test"]);
+    git(&dir, &["config", "user.email", "test@example.com"]);
+    let merge = std::process::Command::new("git")
+      .args(["merge", "-q", "other"])
+      .current_dir(&*dir)
+      .output()
+      .unwrap();
+    assert!(!merge.status.success());
+
+    assert_eq!(staged(&dir), [("file.txt".to_string(), "conflicted")]);
+  }
+}
@@ -3,15 +3,20 @@ use std::path::PathBuf;
 use gix::bstr::ByteSlice;
 
 use crate::repo_cache::with_repo;
+use crate::status_summary::parse_diff_algorithm;
+use crate::whitespace::{normalize_whitespace, IgnoreWhitespace, WhitespaceMode};
 
 /// Default context lines around changes (matches git default).
 const CONTEXT_LINES: u32 = 3;
 
+/// Upper bound for a caller-supplied `context_lines`.
+const MAX_CONTEXT_LINES: u32 = 65535;
+
 /// Maximum file size to diff (10 MB). Larger files return empty string.
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 /// Check first 8KB for null bytes (binary detection).
-fn is_binary(data: &[u8]) -> bool {
+pub(crate) fn is_binary(data: &[u8]) -> bool {
   if data.is_empty() {
     return false;
   }
@@ -19,6 +24,58 @@ fn is_binary(data: &[u8]) -> bool {
   data[..check_len].contains(&0)
 }
 
+/// Optional settings for the file diff functions.
+#[napi(object)]
+#[derive(Debug, Default)]
+pub struct DiffOptions {
+  /// Render changed regions as a word diff in `git diff --word-diff=porcelain`
+  /// format instead of whole `-`/`+` lines.
+  pub word_diff: Option<bool>,
+  /// `"histogram"` (default), `"myers"` or `"minimal"`.
+  pub diff_algorithm: Option<String>,
+  /// Unchanged lines shown around each change (default 3, at most 65535).
+  /// Ignored by `get_full_context_file_diff`.
+  pub context_lines: Option<u32>,
+  /// Whitespace differences to disregard when matching lines. The output
+  /// still shows the original lines.
+  pub ignore_whitespace: Option<WhitespaceMode>,
+  /// Rename detection threshold in percent (0-100, default 50) for
+  /// `get_diff_between_commits`; 0 disables rename detection.
+  pub rename_similarity: Option<u32>,
+}
+
+/// Resolved rendering settings threaded through the diff helpers.
+#[derive(Debug, Clone, Copy)]
+struct FormatOptions {
+  context_lines: u32,
+  word_diff: bool,
+  algorithm: gix::diff::blob::Algorithm,
+  ignore_whitespace: IgnoreWhitespace,
+}
+
+impl FormatOptions {
+  fn new(default_context_lines: u32, options: Option<&DiffOptions>) -> napi::Result<Self> {
+    let context_lines = match options.and_then(|o| o.context_lines) {
+      Some(n) if n > MAX_CONTEXT_LINES => {
+        return Err(napi::Error::from_reason(format!(
+          "context_lines must be at most {MAX_CONTEXT_LINES}, got {n}"
+        )));
+      }
+      Some(n) => n,
+      None => default_context_lines,
+    };
+    Ok(Self {
+      context_lines,
+      word_diff: options.and_then(|o| o.word_diff).unwrap_or(false),
+      algorithm: parse_diff_algorithm(options.and_then(|o| o.diff_algorithm.as_deref()))?,
+      ignore_whitespace: options
+        .and_then(|o| o.ignore_whitespace.as_ref())
+        .map(IgnoreWhitespace::from)
+        .unwrap_or_default(),
+    })
+  }
+}
+
 /// Sink that collects change ranges for later formatting.
 struct UnifiedDiffCollector {
   changes: Vec<(std::ops::Range<u32>, std::ops::Range<u32>)>,
@@ -63,6 +120,106 @@ fn split_lines(data: &[u8]) -> Vec<&[u8]> {
   lines
 }
 
+/// Split lines into word-diff tokens: runs of word characters, runs of
+/// whitespace, and single punctuation bytes, with a `\n` token after every
+/// line. Bytes >= 0x80 count as word characters so UTF-8 sequences stay whole.
+fn tokenize_words<'a>(lines: &[&'a [u8]]) -> Vec<&'a [u8]> {
+  fn class(b: u8) -> u8 {
+    if b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80 {
+      0
+    } else if b == b' ' || b == b'\t' || b == b'\r' {
+      1
+    } else {
+      2
+    }
+  }
+
+  let mut tokens: Vec<&[u8]> = Vec::new();
+  for line in lines {
+    let mut start = 0;
+    while start < line.len() {
+      let kind = class(line[start]);
+      let mut end = start + 1;
+      if kind != 2 {
+        while end < line.len() && class(line[end]) == kind {
+          end += 1;
+        }
+      }
+      tokens.push(&line[start..end]);
+      start = end;
+    }
+    tokens.push(b"\n");
+  }
+  tokens
+}
+
+/// Token sequence adapter so imara-diff can diff words instead of lines.
+struct WordTokens<'a>(&'a [&'a [u8]]);
+
+impl<'a> gix::diff::blob::intern::TokenSource for WordTokens<'a> {
+  type Token = &'a [u8];
+  type Tokenizer = std::iter::Copied<std::slice::Iter<'a, &'a [u8]>>;
+
+  fn tokenize(&self) -> Self::Tokenizer {
+    self.0.iter().copied()
+  }
+
+  fn estimate_tokens(&self) -> u32 {
+    self.0.len() as u32
+  }
+}
+
+/// Append a run of word-diff tokens in porcelain format: text on lines
+/// prefixed with `origin` (`' '`, `'-'` or `'+'`), and a `~` line for each
+/// newline.
+fn push_word_run(output: &mut String, origin: char, tokens: &[&[u8]]) {
+  let mut text: Vec<u8> = Vec::new();
+  for &token in tokens {
+    if token == b"\n" {
+      if !text.is_empty() {
+        output.push(origin);
+        output.push_str(&text.to_str_lossy());
+        output.push('\n');
+        text.clear();
+      }
+      output.push_str("~\n");
+    } else {
+      text.extend_from_slice(token);
+    }
+  }
+  if !text.is_empty() {
+    output.push(origin);
+    output.push_str(&text.to_str_lossy());
+    output.push('\n');
+  }
+}
+
+/// Word-level diff of a changed block of lines, rendered like
+/// `git diff --word-diff=porcelain`.
+fn push_word_diff(
+  output: &mut String,
+  old_lines: &[&[u8]],
+  new_lines: &[&[u8]],
+  algorithm: gix::diff::blob::Algorithm,
+) {
+  let old_tokens = tokenize_words(old_lines);
+  let new_tokens = tokenize_words(new_lines);
+  let input = gix::diff::blob::intern::InternedInput::new(
+    WordTokens(&old_tokens),
+    WordTokens(&new_tokens),
+  );
+  let changes = gix::diff::blob::diff(algorithm, &input, UnifiedDiffCollector::new());
+
+  let mut old_pos = 0usize;
+  for (before, after) in changes {
+    push_word_run(output, ' ', &old_tokens[old_pos..before.start as usize]);
+    push_word_run(output, '-', &old_tokens[before.start as usize..before.end as usize]);
+    push_word_run(output, '+', &new_tokens[after.start as usize..after.end as usize]);
+    old_pos = before.end as usize;
+  }
+  push_word_run(output, ' ', &old_tokens[old_pos..]);
+}
+
 /// Format changes as unified diff hunks with context lines.
 /// Output matches `git diff` format so the client-side parser works.
 fn format_unified_diff(
@@ -73,8 +230,9 @@ fn format_unified_diff(
   new_path: &str,
   is_new_file: bool,
   is_deleted: bool,
-  context_lines: u32,
+  opts: FormatOptions,
 ) -> String {
+  let context_lines = opts.context_lines;
   if changes.is_empty() {
     return String::new();
   }
@@ -111,7 +269,7 @@ fn format_unified_diff(
       let curr_change = &changes[i];
       let gap_old = curr_change.0.start.saturating_sub(prev_change.0.end);
       let gap_new = curr_change.1.start.saturating_sub(prev_change.1.end);
-      if gap_old <= context_lines * 2 || gap_new <= context_lines * 2 {
+      if gap_old <= context_lines.saturating_mul(2) || gap_new <= context_lines.saturating_mul(2) {
         hunks.last_mut().unwrap().push(i);
       } else {
         hunks.push(vec![i]);
@@ -154,14 +312,24 @@ fn format_unified_diff(
       // Context lines before this change
       while old_pos < before.start && new_pos < after.start {
         if let Some(line) = old_lines.get(old_pos as usize) {
-          output.push(' ');
-          output.push_str(&line.to_str_lossy());
-          output.push('\n');
+          push_context_line(&mut output, line, opts.word_diff);
         }
         old_pos += 1;
         new_pos += 1;
       }
 
+      if opts.word_diff {
+        push_word_diff(
+          &mut output,
+          line_range(old_lines, before),
+          line_range(new_lines, after),
+          opts.algorithm,
+        );
+        old_pos = before.end;
+        new_pos = after.end;
+        continue;
+      }
+
       // Removed lines
       for i in before.start..before.end {
         if let Some(line) = old_lines.get(i as usize) {
@@ -186,9 +354,7 @@ fn format_unified_diff(
     // Trailing context
     while old_pos < old_end && new_pos < new_end {
       if let Some(line) = old_lines.get(old_pos as usize) {
-        output.push(' ');
-        output.push_str(&line.to_str_lossy());
-        output.push('\n');
+        push_context_line(&mut output, line, opts.word_diff);
       }
       old_pos += 1;
       new_pos += 1;
@@ -198,6 +364,23 @@ fn format_unified_diff(
   output
 }
 
+/// Append an unchanged line; word-diff porcelain ends every line with `~`.
+fn push_context_line(output: &mut String, line: &[u8], word_diff: bool) {
+  output.push(' ');
+  output.push_str(&line.to_str_lossy());
+  output.push('\n');
+  if word_diff {
+    output.push_str("~\n");
+  }
+}
+
+/// The lines covered by a change range, clamped to the available lines.
+fn line_range<'a, 'b>(lines: &'b [&'a [u8]], range: &std::ops::Range<u32>) -> &'b [&'a [u8]] {
+  let end = (range.end as usize).min(lines.len());
+  let start = (range.start as usize).min(end);
+  &lines[start..end]
+}
+
 /// Compute diff between two byte slices and format as unified diff.
 fn compute_and_format(
   old: &[u8],
@@ -205,8 +388,9 @@ fn compute_and_format(
   path: &str,
   is_new: bool,
   is_deleted: bool,
+  opts: FormatOptions,
 ) -> String {
-  compute_and_format_with_context(old, new, path, is_new, is_deleted, CONTEXT_LINES)
+  compute_and_format_with_context(old, new, path, is_new, is_deleted, opts)
 }
 
 /// Compute diff with configurable context lines. Use u32::MAX for full-file context.
@@ -216,7 +400,7 @@ fn compute_and_format_with_context(
   path: &str,
   is_new: bool,
   is_deleted: bool,
-  context_lines: u32,
+  opts: FormatOptions,
 ) -> String {
   if is_binary(old) || is_binary(new) {
     return format!(
@@ -228,12 +412,15 @@ fn compute_and_format_with_context(
   let old_lines = split_lines(old);
   let new_lines = split_lines(new);
 
-  let input = gix::diff::blob::intern::InternedInput::new(old, new);
+  // Match lines on their normalized form but print the originals; the line
+  // structure is unchanged by normalization, so the indices line up.
+  let old_normalized = normalize_whitespace(old, opts.ignore_whitespace);
+  let new_normalized = normalize_whitespace(new, opts.ignore_whitespace);
+  let input = gix::diff::blob::intern::InternedInput::new(&*old_normalized, &*new_normalized);
   let collector = UnifiedDiffCollector::new();
-  let changes =
-    gix::diff::blob::diff(gix::diff::blob::Algorithm::Histogram, &input, collector);
+  let changes = gix::diff::blob::diff(opts.algorithm, &input, collector);
 
-  format_unified_diff(&old_lines, &new_lines, &changes, path, path, is_new, is_deleted, context_lines)
+  format_unified_diff(&old_lines, &new_lines, &changes, path, path, is_new, is_deleted, opts)
 }
 
 #[napi]
@@ -241,8 +428,9 @@ pub async fn get_single_file_diff(
   cwd: String,
   file_path: String,
   staged: bool,
+  options: Option<DiffOptions>,
 ) -> napi::Result<String> {
-  diff_file_with_context(&cwd, &file_path, staged, CONTEXT_LINES)
+  diff_file_with_context(&cwd, &file_path, staged, FormatOptions::new(CONTEXT_LINES, options.as_ref())?)
 }
 
 /// Full-context diff (equivalent to git diff -U99999). Shows all lines of the file.
@@ -251,21 +439,26 @@ pub async fn get_full_context_file_diff(
   cwd: String,
   file_path: String,
   staged: bool,
+  options: Option<DiffOptions>,
 ) -> napi::Result<String> {
-  diff_file_with_context(&cwd, &file_path, staged, u32::MAX)
+  let opts = FormatOptions {
+    context_lines: u32::MAX,
+    ..FormatOptions::new(CONTEXT_LINES, options.as_ref())?
+  };
+  diff_file_with_context(&cwd, &file_path, staged, opts)
 }
 
 fn diff_file_with_context(
   cwd: &str,
   file_path: &str,
   staged: bool,
-  context_lines: u32,
+  opts: FormatOptions,
 ) -> napi::Result<String> {
   with_repo(cwd, |repo| {
     let worktree_path = PathBuf::from(cwd);
 
     if staged {
-      return diff_staged_file_ctx(repo, file_path, context_lines);
+      return diff_staged_file_ctx(repo, file_path, opts);
     }
 
     // Check if file is tracked via index
@@ -279,18 +472,18 @@ fn diff_file_with_context(
       .any(|entry| entry.path(&index).to_str_lossy() == file_path);
 
     if !is_tracked {
-      return diff_untracked_file_ctx(&worktree_path, file_path, context_lines);
+      return diff_untracked_file_ctx(&worktree_path, file_path, opts);
     }
 
-    diff_unstaged_file_ctx(repo, &worktree_path, file_path, &index, context_lines)
+    diff_unstaged_file_ctx(repo, &worktree_path, file_path, &index, opts)
   })
 }
 
 fn diff_staged_file(repo: &gix::Repository, file_path: &str) -> napi::Result<String> {
-  diff_staged_file_ctx(repo, file_path, CONTEXT_LINES)
+  diff_staged_file_ctx(repo, file_path, FormatOptions::new(CONTEXT_LINES, None)?)
 }
 
-fn diff_staged_file_ctx(repo: &gix::Repository, file_path: &str, context_lines: u32) -> napi::Result<String> {
+fn diff_staged_file_ctx(repo: &gix::Repository, file_path: &str, opts: FormatOptions) -> napi::Result<String> {
   // Get blob from HEAD tree
   let old_data: Option<Vec<u8>> = (|| {
     let head = repo.head_commit().ok()?;
@@ -322,7 +515,7 @@ fn diff_staged_file_ctx(repo: &gix::Repository, file_path: &str, context_lines:
     file_path,
     old_data.is_none(),
     new_data.is_none(),
-    context_lines,
+    opts,
   ))
 }
 
@@ -332,7 +525,7 @@ fn diff_unstaged_file(
   file_path: &str,
   index: &gix::index::File,
 ) -> napi::Result<String> {
-  diff_unstaged_file_ctx(repo, worktree_path, file_path, index, CONTEXT_LINES)
+  diff_unstaged_file_ctx(repo, worktree_path, file_path, index, FormatOptions::new(CONTEXT_LINES, None)?)
 }
 
 fn diff_unstaged_file_ctx(
@@ -340,7 +533,7 @@ fn diff_unstaged_file_ctx(
   worktree_path: &PathBuf,
   file_path: &str,
   index: &gix::index::File,
-  context_lines: u32,
+  opts: FormatOptions,
 ) -> napi::Result<String> {
   // Get blob from index
   let old_data: Option<Vec<u8>> = index
@@ -374,15 +567,15 @@ fn diff_unstaged_file_ctx(
     file_path,
     false,
     new_data.is_none(),
-    context_lines,
+    opts,
   ))
 }
 
 fn diff_untracked_file(worktree_path: &PathBuf, file_path: &str) -> napi::Result<String> {
-  diff_untracked_file_ctx(worktree_path, file_path, CONTEXT_LINES)
+  diff_untracked_file_ctx(worktree_path, file_path, FormatOptions::new(CONTEXT_LINES, None)?)
 }
 
-fn diff_untracked_file_ctx(worktree_path: &PathBuf, file_path: &str, context_lines: u32) -> napi::Result<String> {
+fn diff_untracked_file_ctx(worktree_path: &PathBuf, file_path: &str, opts: FormatOptions) -> napi::Result<String> {
   let disk_path = worktree_path.join(file_path);
 
   // Size guard
@@ -399,7 +592,7 @@ fn diff_untracked_file_ctx(worktree_path: &PathBuf, file_path: &str, context_lin
     return Ok(String::new());
   }
 
-  Ok(compute_and_format_with_context(b"", &data, file_path, true, false, context_lines))
+  Ok(compute_and_format_with_context(b"", &data, file_path, true, false, opts))
 }
 
 #[napi]
@@ -407,6 +600,7 @@ pub async fn get_commit_file_diff(
   cwd: String,
   hash: String,
   file_path: String,
+  options: Option<DiffOptions>,
 ) -> napi::Result<String> {
   with_repo(&cwd, |repo| {
     let commit_id = repo
@@ -457,6 +651,7 @@ pub async fn get_commit_file_diff(
       &file_path,
       parent_blob.is_none(),
       commit_blob.is_none(),
+      FormatOptions::new(CONTEXT_LINES, options.as_ref())?,
     ))
   })
 }
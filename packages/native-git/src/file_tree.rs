@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use gix::bstr::{BString, ByteSlice};
+
+use crate::repo_cache::with_repo;
+use crate::status_summary::{is_excluded, shared_excludes};
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TreeEntry {
+  /// Repo-relative path, including the requested prefix.
+  pub path: String,
+  /// `"blob"` (files and symlinks), `"tree"` or `"commit"` (submodules).
+  pub kind: String,
+  /// Octal mode as `git ls-tree` prints it, e.g. `100644` or `040000`.
+  pub mode: String,
+  pub hash: String,
+  /// Blob size in bytes; `None` for trees and submodules.
+  pub size: Option<i64>,
+}
+
+/// List the entries directly under `prefix` (the root when `None`) in the
+/// tree of `rev`, one level deep. Blob sizes come from the object header,
+/// so file contents are never read.
+#[napi]
+pub async fn get_file_tree(
+  cwd: String,
+  rev: String,
+  prefix: Option<String>,
+) -> napi::Result<Vec<TreeEntry>> {
+  with_repo(&cwd, |repo| {
+    let root = repo
+      .rev_parse_single(rev.as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to parse revision '{rev}': {e}")))?
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+      .peel_to_tree()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get tree of '{rev}': {e}")))?;
+
+    let prefix = prefix
+      .as_deref()
+      .map(|p| p.trim_matches('/'))
+      .filter(|p| !p.is_empty());
+    let tree = match prefix {
+      None => root,
+      Some(prefix) => root
+        .lookup_entry_by_path(prefix)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to look up {prefix}: {e}")))?
+        .filter(|entry| entry.mode().is_tree())
+        .ok_or_else(|| napi::Error::from_reason(format!("Not a directory in '{rev}': {prefix}")))?
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read tree {prefix}: {e}")))?
+        .into_tree(),
+    };
+
+    let mut entries: Vec<TreeEntry> = Vec::new();
+    for entry in tree.iter() {
+      let entry =
+        entry.map_err(|e| napi::Error::from_reason(format!("Failed to read tree entry: {e}")))?;
+      let name = entry.filename().to_str_lossy();
+      let path = match prefix {
+        Some(prefix) => format!("{prefix}/{name}"),
+        None => name.to_string(),
+      };
+
+      let mode = entry.mode();
+      let id = entry.oid().to_owned();
+      let (kind, size) = if mode.is_tree() {
+        ("tree", None)
+      } else if mode.is_commit() {
+        ("commit", None)
+      } else {
+        let header = repo
+          .find_header(id)
+          .map_err(|e| napi::Error::from_reason(format!("Failed to read {path}: {e}")))?;
+        ("blob", Some(header.size() as i64))
+      };
+
+      entries.push(TreeEntry {
+        path,
+        kind: kind.to_string(),
+        mode: format!("{:06o}", mode.value()),
+        hash: id.to_string(),
+        size,
+      });
+    }
+
+    Ok(entries)
+  })
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct UntrackedNode {
+  /// Repo-relative path.
+  pub path: String,
+  pub is_dir: bool,
+  /// Empty for files, and for directories at `max_depth`.
+  pub children: Vec<UntrackedNode>,
+}
+
+/// Untracked files grouped by directory while building the tree.
+#[derive(Default)]
+struct UntrackedDir {
+  dirs: BTreeMap<String, UntrackedDir>,
+  files: BTreeSet<String>,
+}
+
+impl UntrackedDir {
+  /// Add a file, or only its directory at `max_depth` when it lies deeper.
+  fn insert(&mut self, components: &[&str], max_depth: usize) {
+    let mut dir = self;
+    for (depth, component) in components.iter().enumerate() {
+      let is_file = depth + 1 == components.len();
+      if is_file && depth < max_depth {
+        dir.files.insert(component.to_string());
+        return;
+      }
+      dir = dir.dirs.entry(component.to_string()).or_default();
+      if depth + 1 == max_depth {
+        return;
+      }
+    }
+  }
+
+  fn into_nodes(self, prefix: &str) -> Vec<UntrackedNode> {
+    let join = |name: &str| match prefix {
+      "" => name.to_string(),
+      _ => format!("{prefix}/{name}"),
+    };
+    let mut nodes: Vec<UntrackedNode> = Vec::new();
+    for (name, dir) in self.dirs {
+      let path = join(&name);
+      nodes.push(UntrackedNode {
+        children: dir.into_nodes(&path),
+        path,
+        is_dir: true,
+      });
+    }
+    for name in self.files {
+      nodes.push(UntrackedNode {
+        path: join(&name),
+        is_dir: false,
+        children: Vec::new(),
+      });
+    }
+    nodes
+  }
+}
+
+/// Untracked files in the worktree at `cwd` as a tree, built from the
+/// status scan rather than a separate directory walk. Each level lists
+/// directories first, then files, by name. With `max_depth`, directories
+/// at that depth (top-level entries are at depth 1) are returned without
+/// their contents.
+#[napi]
+pub async fn get_untracked_tree(
+  cwd: String,
+  max_depth: Option<u32>,
+) -> napi::Result<Vec<UntrackedNode>> {
+  with_repo(&cwd, |repo| {
+    let status_iter = repo
+      .status(gix::progress::Discard)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to create status: {e}")))?
+      .untracked_files(gix::status::UntrackedFiles::Files)
+      .into_index_worktree_iter(Vec::<BString>::new())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
+
+    let max_depth = max_depth.map_or(usize::MAX, |depth| depth.max(1) as usize);
+    let excludes = shared_excludes(repo);
+    let mut root = UntrackedDir::default();
+    for entry in status_iter {
+      let entry =
+        entry.map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
+      let gix::status::index_worktree::Item::DirectoryContents { entry: dir, .. } = &entry else {
+        continue;
+      };
+      let rela_path = dir.rela_path.to_str_lossy();
+      if let Some(search) = &excludes {
+        if is_excluded(search, &rela_path) {
+          continue;
+        }
+      }
+      let components: Vec<&str> = rela_path.split('/').collect();
+      root.insert(&components, max_depth);
+    }
+
+    Ok(root.into_nodes(""))
+  })
+}
@@ -0,0 +1,30 @@
+use std::process::Command;
+
+/// Run `git <args>` in `cwd` and return its trimmed stdout, or an error
+/// carrying git's output (merge commands report conflicts on stdout).
+///
+/// For what gix doesn't cover: moving a branch together with its reflog and
+/// config section (`branch -m`), setting or unsetting a branch's upstream,
+/// editing repository config (`config --local`) and walking revision ranges
+/// (`rev-list`).
+pub(crate) fn run_git(cwd: &str, args: &[&str]) -> napi::Result<String> {
+  let output = Command::new("git")
+    .args(args)
+    .current_dir(cwd)
+    .output()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to run git: {e}")))?;
+  if !output.status.success() {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let details: Vec<&str> = [stdout.trim(), stderr.trim()]
+      .into_iter()
+      .filter(|s| !s.is_empty())
+      .collect();
+    return Err(napi::Error::from_reason(format!(
+      "git {} failed: {}",
+      args.first().copied().unwrap_or_default(),
+      details.join("\n")
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
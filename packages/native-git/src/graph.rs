@@ -0,0 +1,166 @@
+use crate::log::log_entry;
+use crate::repo_cache::with_repo;
+
+/// Default number of commits returned by `get_branch_graph`.
+const DEFAULT_GRAPH_LIMIT: u32 = 500;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct MergeLine {
+  pub from_column: u32,
+  pub to_column: u32,
+  /// Commit at which the line starts or ends.
+  pub commit_hash: String,
+}
+
+/// A `GitLogEntry` plus the data needed to draw it in a commit graph.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GraphCommit {
+  pub hash: String,
+  pub short_hash: String,
+  pub author: String,
+  pub relative_date: String,
+  pub message: String,
+  pub parent_hashes: Vec<String>,
+  /// Lane the commit is drawn in, 0 being leftmost.
+  pub column: u32,
+  /// Lines joining this commit's lane with other lanes: branches that end
+  /// here (`from_column` is the other lane) and merge parents that continue
+  /// in another lane (`to_column` is the other lane).
+  pub merge_lines: Vec<MergeLine>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommitGraph {
+  pub commits: Vec<GraphCommit>,
+}
+
+/// Greedy lane allocation: each lane holds the hash of the commit it is
+/// waiting for, and new lanes reuse the leftmost free slot so lines stay
+/// close to the left edge.
+#[derive(Default)]
+struct Lanes(Vec<Option<String>>);
+
+impl Lanes {
+  fn position(&self, hash: &str) -> Option<usize> {
+    self.0.iter().position(|lane| lane.as_deref() == Some(hash))
+  }
+
+  fn allocate(&mut self, hash: String) -> usize {
+    match self.0.iter().position(Option::is_none) {
+      Some(free) => {
+        self.0[free] = Some(hash);
+        free
+      }
+      None => {
+        self.0.push(Some(hash));
+        self.0.len() - 1
+      }
+    }
+  }
+
+  /// Assign `hash` a column and update the lanes for its parents, returning
+  /// the column and the lines to draw at this commit.
+  fn place(&mut self, hash: &str, parents: &[String]) -> (usize, Vec<MergeLine>) {
+    let column = match self.position(hash) {
+      Some(column) => column,
+      // A branch tip nothing has pointed at yet.
+      None => self.allocate(hash.to_string()),
+    };
+
+    let mut lines = Vec::new();
+    // Other lanes waiting for this commit end here.
+    for (lane, waiting) in self.0.iter_mut().enumerate() {
+      if lane != column && waiting.as_deref() == Some(hash) {
+        *waiting = None;
+        lines.push(MergeLine {
+          from_column: lane as u32,
+          to_column: column as u32,
+          commit_hash: hash.to_string(),
+        });
+      }
+    }
+
+    // The first parent continues straight down; others get their own lane
+    // unless one is already waiting for them.
+    self.0[column] = parents.first().cloned();
+    for parent in parents.iter().skip(1) {
+      let lane = match self.position(parent) {
+        Some(lane) => lane,
+        None => self.allocate(parent.clone()),
+      };
+      lines.push(MergeLine {
+        from_column: column as u32,
+        to_column: lane as u32,
+        commit_hash: hash.to_string(),
+      });
+    }
+
+    // Drop trailing free lanes so the graph doesn't keep growing wider.
+    while self.0.last().is_some_and(Option::is_none) {
+      self.0.pop();
+    }
+
+    (column, lines)
+  }
+}
+
+/// Commits reachable from HEAD and `include_refs` (newest first, at most
+/// `limit`, default 500) with parent edges and lane assignments for drawing
+/// a branch graph.
+#[napi]
+pub async fn get_branch_graph(
+  cwd: String,
+  limit: Option<u32>,
+  include_refs: Option<Vec<String>>,
+) -> napi::Result<CommitGraph> {
+  with_repo(&cwd, |repo| {
+    let head_commit = repo
+      .head_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+    let mut tips = vec![head_commit.id];
+    for name in include_refs.unwrap_or_default() {
+      let id = repo
+        .rev_parse_single(name.as_str())
+        .map_err(|e| napi::Error::from_reason(format!("Failed to parse revision '{name}': {e}")))?;
+      tips.push(id.detach());
+    }
+
+    let max = limit.unwrap_or(DEFAULT_GRAPH_LIMIT) as usize;
+    let iter = repo
+      .rev_walk(tips)
+      .sorting(gix::revision::walk::Sorting::ByCommitTime(
+        Default::default(),
+      ))
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+
+    let mut lanes = Lanes::default();
+    let mut commits: Vec<GraphCommit> = Vec::new();
+    for commit_info in iter.take(max) {
+      let info =
+        commit_info.map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
+      let commit = info
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+
+      let entry = log_entry(&commit);
+      let parent_hashes: Vec<String> = commit.parent_ids().map(|id| id.to_string()).collect();
+      let (column, merge_lines) = lanes.place(&entry.hash, &parent_hashes);
+      commits.push(GraphCommit {
+        hash: entry.hash,
+        short_hash: entry.short_hash,
+        author: entry.author,
+        relative_date: entry.relative_date,
+        message: entry.message,
+        parent_hashes,
+        column: column as u32,
+        merge_lines,
+      });
+    }
+
+    Ok(CommitGraph { commits })
+  })
+}
@@ -0,0 +1,84 @@
+use std::path::{Path, PathBuf};
+
+use crate::repo_cache::with_repo;
+
+// Output only: napi converts `u64` to a BigInt but not back.
+#[napi(object, object_from_js = false)]
+#[derive(Debug, Clone)]
+pub struct HookEntry {
+  pub name: String,
+  pub path: String,
+  pub is_executable: bool,
+  pub size_bytes: u64,
+}
+
+#[cfg(unix)]
+fn is_executable(_path: &Path, metadata: &std::fs::Metadata) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  metadata.permissions().mode() & 0o111 != 0
+}
+
+/// Windows has no executable bit; go by extension instead.
+#[cfg(not(unix))]
+fn is_executable(path: &Path, _metadata: &std::fs::Metadata) -> bool {
+  path
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .is_some_and(|ext| ext.eq_ignore_ascii_case("bat") || ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Files directly inside `dir`, sorted by name. A missing directory has none.
+fn hooks_in(dir: &Path) -> Vec<HookEntry> {
+  let Ok(read_dir) = std::fs::read_dir(dir) else {
+    return Vec::new();
+  };
+
+  let mut hooks: Vec<HookEntry> = Vec::new();
+  for entry in read_dir.flatten() {
+    let path = entry.path();
+    // Follows symlinks, so linked hooks report their target's mode and size.
+    let Ok(metadata) = std::fs::metadata(&path) else {
+      continue;
+    };
+    if !metadata.is_file() {
+      continue;
+    }
+    hooks.push(HookEntry {
+      name: entry.file_name().to_string_lossy().to_string(),
+      path: path.to_string_lossy().to_string(),
+      is_executable: is_executable(&path, &metadata),
+      size_bytes: metadata.len(),
+    });
+  }
+  hooks.sort_by(|a, b| a.name.cmp(&b.name));
+  hooks
+}
+
+/// Files in the repository's hooks directories: `.git/hooks` and, when set,
+/// `core.hooksPath` (relative paths resolve against the worktree root, as in
+/// git). Every file is listed whatever its name, including `*.sample`
+/// files; git only runs executable files named after a hook from the active
+/// directory (`core.hooksPath` if set). Standard hooks come first.
+#[napi]
+pub async fn get_hooks(cwd: String) -> napi::Result<Vec<HookEntry>> {
+  with_repo(&cwd, |repo| {
+    let mut dirs: Vec<PathBuf> = vec![repo.common_dir().join("hooks")];
+
+    let config = repo.config_snapshot();
+    if let Some(hooks_path) = config.trusted_path("core.hooksPath") {
+      let hooks_path =
+        hooks_path.map_err(|e| napi::Error::from_reason(format!("Invalid core.hooksPath: {e}")))?;
+      let base = repo.workdir().unwrap_or_else(|| repo.git_dir());
+      let dir = base.join(hooks_path);
+      let same_dir = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+      };
+      if !same_dir(&dir, &dirs[0]) {
+        dirs.push(dir);
+      }
+    }
+
+    Ok(dirs.iter().flat_map(|dir| hooks_in(dir)).collect())
+  })
+}
@@ -1,22 +1,67 @@
 #[macro_use]
 extern crate napi_derive;
 
+mod attributes;
+mod git_cli;
 mod repo_cache;
+mod status_cache;
 mod status_summary;
 mod diff_summary;
 mod branch;
+mod checkout;
+mod cherry_pick;
 mod log;
+mod object_info;
+mod operation_state;
+mod pack_stats;
+mod path_attributes;
 mod file_diff;
+mod file_tree;
+mod graph;
+mod hooks;
 mod commit_info;
+mod commit_diff;
+mod config;
+mod contributors;
+mod conflicts;
+mod ref_name;
 mod reset;
+mod restore;
+mod signature;
+mod staged_stats;
+mod stash;
+mod tags;
+mod whitespace;
+#[cfg(test)]
+mod test_util;
 
+pub use status_cache::*;
 pub use status_summary::*;
 pub use diff_summary::*;
 pub use branch::*;
+pub use cherry_pick::*;
 pub use log::*;
+pub use object_info::*;
+pub use operation_state::*;
+pub use pack_stats::*;
+pub use path_attributes::*;
 pub use file_diff::*;
+pub use file_tree::*;
+pub use graph::*;
+pub use hooks::*;
 pub use commit_info::*;
+pub use commit_diff::*;
+pub use config::*;
+pub use contributors::*;
+pub use conflicts::*;
+pub use ref_name::*;
 pub use reset::*;
+pub use restore::*;
+pub use signature::*;
+pub use staged_stats::*;
+pub use stash::*;
+pub use tags::*;
+pub use whitespace::*;
 
 /// Simple ping function to verify the native module loads correctly.
 #[napi]
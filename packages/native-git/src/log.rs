@@ -1,8 +1,11 @@
+use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
 
-use gix::bstr::ByteSlice;
+use gix::bstr::{BString, ByteSlice};
 
+use crate::git_cli::run_git;
 use crate::repo_cache::with_repo;
+use crate::status_summary::upstream_ref_name;
 
 #[napi(object)]
 #[derive(Debug, Clone)]
@@ -15,7 +18,7 @@ pub struct GitLogEntry {
 }
 
 /// Format a timestamp as a relative date string (e.g. "2 hours ago", "3 days ago").
-fn format_relative_date(seconds_since_epoch: i64) -> String {
+pub(crate) fn format_relative_date(seconds_since_epoch: i64) -> String {
   let now = SystemTime::now()
     .duration_since(SystemTime::UNIX_EPOCH)
     .map(|d| d.as_secs() as i64)
@@ -65,64 +68,235 @@ fn format_relative_date(seconds_since_epoch: i64) -> String {
   format!("{} years ago", years)
 }
 
+/// Build the log entry shown for `commit`.
+pub(crate) fn log_entry(commit: &gix::Commit<'_>) -> GitLogEntry {
+  let hash = commit.id().to_string();
+  let short_hash = hash[..7.min(hash.len())].to_string();
+
+  let author_sig = commit.author().ok();
+
+  let author_name = author_sig
+    .as_ref()
+    .map(|a| a.name.to_string())
+    .unwrap_or_default();
+
+  let time_seconds = author_sig
+    .as_ref()
+    .and_then(|a| a.time().ok())
+    .map(|t| t.seconds)
+    .unwrap_or(0);
+
+  let relative_date = format_relative_date(time_seconds);
+
+  // message_raw_sloppy returns &BStr, use ByteSlice::lines()
+  let raw_message = commit.message_raw_sloppy();
+  let message = raw_message
+    .lines()
+    .next()
+    .map(|l| l.to_str_lossy().to_string())
+    .unwrap_or_default();
+
+  GitLogEntry {
+    hash,
+    short_hash,
+    author: author_name,
+    relative_date,
+    message,
+  }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct GetLogOptions {
+  /// Order commits like `git log --topo-order`: every commit before its
+  /// parents, and the commits of a merged branch kept together. Default is
+  /// the plain walk order.
+  pub topological_order: Option<bool>,
+}
+
+/// The most recent commits reachable from HEAD (default 20).
 #[napi]
-pub async fn get_log(cwd: String, limit: Option<u32>) -> napi::Result<Vec<GitLogEntry>> {
+pub async fn get_log(
+  cwd: String,
+  limit: Option<u32>,
+  options: Option<GetLogOptions>,
+) -> napi::Result<Vec<GitLogEntry>> {
+  let topological = options.and_then(|o| o.topological_order).unwrap_or(false);
+  with_repo(&cwd, |repo| {
+    log_entries(repo, limit.unwrap_or(20) as usize, topological)
+  })
+}
+
+fn log_entries(
+  repo: &gix::Repository,
+  max: usize,
+  topological: bool,
+) -> napi::Result<Vec<GitLogEntry>> {
+  let head_commit = repo
+    .head_commit()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+
+  let ids: Vec<gix::ObjectId> = if topological {
+    use gix::traverse::commit::topo;
+    topo::Builder::from_iters(&repo.objects, [head_commit.id], None::<Vec<gix::ObjectId>>)
+      .sorting(topo::Sorting::TopoOrder)
+      .build()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?
+      .take(max)
+      .map(|info| {
+        info
+          .map(|info| info.id)
+          .map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))
+      })
+      .collect::<napi::Result<_>>()?
+  } else {
+    repo
+      .rev_walk([head_commit.id()])
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?
+      .take(max)
+      .map(|info| {
+        info
+          .map(|info| info.id)
+          .map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))
+      })
+      .collect::<napi::Result<_>>()?
+  };
+
+  ids
+    .into_iter()
+    .map(|id| {
+      repo
+        .find_commit(id)
+        .map(|commit| log_entry(&commit))
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))
+    })
+    .collect()
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LogPage {
+  pub entries: Vec<GitLogEntry>,
+  /// Pass back as `cursor` to get the next page; `None` on the last page.
+  pub next_cursor: Option<String>,
+}
+
+/// One page of `get_log`: up to `page_size` commits following `cursor` (the
+/// hash of the last commit of the previous page) in HEAD's history, or the
+/// first page without a cursor. Nothing is kept between calls: every page
+/// walks from HEAD and skips commits up to the cursor, so it costs O(n) in
+/// the number of commits before it.
+#[napi]
+pub async fn get_log_page(
+  cwd: String,
+  cursor: Option<String>,
+  page_size: u32,
+) -> napi::Result<LogPage> {
   with_repo(&cwd, |repo| {
     let head_commit = repo
       .head_commit()
       .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+    let cursor_id = cursor
+      .as_deref()
+      .map(|hash| {
+        gix::ObjectId::from_hex(hash.as_bytes())
+          .map_err(|e| napi::Error::from_reason(format!("Invalid cursor '{hash}': {e}")))
+      })
+      .transpose()?;
 
-    let max = limit.unwrap_or(20) as usize;
-    let mut entries: Vec<GitLogEntry> = Vec::with_capacity(max);
-
-    let walk = repo.rev_walk([head_commit.id()]);
-    let iter = walk
+    let mut iter = repo
+      .rev_walk([head_commit.id()])
       .all()
-      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?
+      .map(|info| {
+        info
+          .map(|info| info.id)
+          .map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))
+      });
 
-    for commit_info in iter {
-      if entries.len() >= max {
-        break;
+    if let Some(cursor_id) = cursor_id {
+      loop {
+        match iter.next().transpose()? {
+          Some(id) if id == cursor_id => break,
+          Some(_) => continue,
+          None => {
+            return Err(napi::Error::from_reason(format!(
+              "Cursor {cursor_id} is not in HEAD's history"
+            )))
+          }
+        }
       }
-      let info = commit_info
-        .map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
+    }
 
+    let page_size = page_size as usize;
+    let mut entries: Vec<GitLogEntry> = Vec::with_capacity(page_size);
+    for id in iter.by_ref().take(page_size) {
+      let commit = repo
+        .find_commit(id?)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+      entries.push(log_entry(&commit));
+    }
+
+    // Only hand out a cursor if there is something after it.
+    let has_more = iter.next().transpose()?.is_some();
+    let next_cursor = match entries.last() {
+      Some(last) if has_more => Some(last.hash.clone()),
+      _ => None,
+    };
+    Ok(LogPage {
+      entries,
+      next_cursor,
+    })
+  })
+}
+
+/// A `GitLogEntry` plus its parents, for callers that draw their own graph.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct LogGraphEntry {
+  pub hash: String,
+  pub short_hash: String,
+  pub author: String,
+  pub relative_date: String,
+  pub message: String,
+  /// Empty for root commits, two or more for merges.
+  pub parent_hashes: Vec<String>,
+}
+
+/// `get_log` with each entry's parent hashes, so the caller can lay out
+/// lanes and merge edges. Same walk and default limit (20) as `get_log`,
+/// which stays the cheaper choice when no graph is drawn. For lanes computed
+/// natively, see `get_branch_graph`.
+#[napi]
+pub async fn get_log_graph(cwd: String, limit: Option<u32>) -> napi::Result<Vec<LogGraphEntry>> {
+  with_repo(&cwd, |repo| {
+    let head_commit = repo
+      .head_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+
+    let max = limit.unwrap_or(20) as usize;
+    let iter = repo
+      .rev_walk([head_commit.id()])
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+
+    let mut entries: Vec<LogGraphEntry> = Vec::with_capacity(max);
+    for commit_info in iter.take(max) {
+      let info =
+        commit_info.map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
       let commit = info
         .object()
         .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
 
-      let hash = commit.id().to_string();
-      let short_hash = hash[..7.min(hash.len())].to_string();
-
-      let author_sig = commit.author().ok();
-
-      let author_name = author_sig
-        .as_ref()
-        .map(|a| a.name.to_string())
-        .unwrap_or_default();
-
-      let time_seconds = author_sig
-        .as_ref()
-        .and_then(|a| a.time().ok())
-        .map(|t| t.seconds)
-        .unwrap_or(0);
-
-      let relative_date = format_relative_date(time_seconds);
-
-      // message_raw_sloppy returns &BStr, use ByteSlice::lines()
-      let raw_message = commit.message_raw_sloppy();
-      let message = raw_message
-        .lines()
-        .next()
-        .map(|l| l.to_str_lossy().to_string())
-        .unwrap_or_default();
-
-      entries.push(GitLogEntry {
-        hash,
-        short_hash,
-        author: author_name,
-        relative_date,
-        message,
+      let entry = log_entry(&commit);
+      entries.push(LogGraphEntry {
+        hash: entry.hash,
+        short_hash: entry.short_hash,
+        author: entry.author,
+        relative_date: entry.relative_date,
+        message: entry.message,
+        parent_hashes: commit.parent_ids().map(|id| id.to_string()).collect(),
       });
     }
 
@@ -130,6 +304,70 @@ pub async fn get_log(cwd: String, limit: Option<u32>) -> napi::Result<Vec<GitLog
   })
 }
 
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommitDetails {
+  pub hash: String,
+  pub short_hash: String,
+  pub author_name: String,
+  pub author_email: String,
+  pub committer_name: String,
+  pub committer_email: String,
+  /// Author date, in seconds since the Unix epoch.
+  pub timestamp_secs: i64,
+  /// Subject line.
+  pub message: String,
+  /// Everything after the subject line, trimmed.
+  pub body: String,
+  pub parent_hashes: Vec<String>,
+}
+
+/// Full details of a single commit. `hash` can be anything `rev-parse`
+/// accepts (full or short hash, branch, tag, `HEAD~2`, ...); a revision that
+/// doesn't resolve to a commit fails with "Commit not found".
+#[napi]
+pub async fn get_commit(cwd: String, hash: String) -> napi::Result<CommitDetails> {
+  with_repo(&cwd, |repo| {
+    let commit = repo
+      .rev_parse_single(hash.as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Commit not found: {hash} ({e})")))?
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+      .peel_to_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Commit not found: {hash} ({e})")))?;
+
+    let full_hash = commit.id().to_string();
+    let short_hash = full_hash[..7.min(full_hash.len())].to_string();
+
+    let author = commit
+      .author()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read author: {e}")))?;
+    let committer = commit
+      .committer()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read committer: {e}")))?;
+    let timestamp_secs = author.time().map(|t| t.seconds).unwrap_or(0);
+
+    let raw = commit.message_raw_sloppy().to_str_lossy();
+    let (message, body) = match raw.split_once('\n') {
+      Some((subject, rest)) => (subject.trim().to_string(), rest.trim().to_string()),
+      None => (raw.trim().to_string(), String::new()),
+    };
+
+    Ok(CommitDetails {
+      hash: full_hash,
+      short_hash,
+      author_name: author.name.to_string(),
+      author_email: author.email.to_string(),
+      committer_name: committer.name.to_string(),
+      committer_email: committer.email.to_string(),
+      timestamp_secs,
+      message,
+      body,
+      parent_hashes: commit.parent_ids().map(|id| id.to_string()).collect(),
+    })
+  })
+}
+
 #[napi]
 pub async fn get_commit_body(cwd: String, hash: String) -> napi::Result<String> {
   with_repo(&cwd, |repo| {
@@ -201,3 +439,240 @@ pub async fn get_unpushed_hashes(cwd: String) -> napi::Result<Vec<String>> {
     Ok(unpushed)
   })
 }
+
+/// Default cap for `list_unpushed_commits`.
+const DEFAULT_UNPUSHED_LIMIT: u32 = 50;
+
+/// The commits `get_status_summary` counts as unpushed, newest first: those
+/// in `<upstream>..HEAD`, or `<base_branch>..HEAD` when the branch has no
+/// upstream. Empty on a detached HEAD or with neither to compare against.
+/// At most `limit` (default 50) are returned.
+#[napi]
+pub async fn list_unpushed_commits(
+  cwd: String,
+  base_branch: Option<String>,
+  limit: Option<u32>,
+) -> napi::Result<Vec<GitLogEntry>> {
+  with_repo(&cwd, |repo| {
+    let branch = match repo.head_ref() {
+      Ok(Some(head_ref)) => head_ref.name().shorten().to_string(),
+      _ => return Ok(Vec::new()),
+    };
+    let upstream = upstream_ref_name(repo, &branch);
+    let range = if repo.find_reference(upstream.as_str()).is_ok() {
+      format!("{}..HEAD", upstream)
+    } else if let Some(base) = &base_branch {
+      format!("{}..HEAD", base)
+    } else {
+      return Ok(Vec::new());
+    };
+
+    // Same `git rev-list` range as the status count, so the two agree.
+    let max_count = format!("--max-count={}", limit.unwrap_or(DEFAULT_UNPUSHED_LIMIT));
+    let hashes = run_git(&cwd, &["rev-list", &max_count, &range])?;
+
+    let mut entries: Vec<GitLogEntry> = Vec::new();
+    for hash in hashes.lines() {
+      let id = gix::ObjectId::from_hex(hash.as_bytes())
+        .map_err(|e| napi::Error::from_reason(format!("Invalid commit hash {hash}: {e}")))?;
+      let commit = repo
+        .find_commit(id)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+      entries.push(log_entry(&commit));
+    }
+    Ok(entries)
+  })
+}
+
+/// Default cap on commits visited by `last_commits_for_dir`.
+const DEFAULT_DIR_HISTORY_LIMIT: u32 = 10_000;
+
+/// Tree id of `dir` in `commit_id` and the ids of its immediate children by
+/// name, or `None` if the commit has no such directory.
+type DirSnapshot = (gix::ObjectId, HashMap<BString, gix::ObjectId>);
+
+fn dir_snapshot(
+  repo: &gix::Repository,
+  commit_id: gix::ObjectId,
+  dir: &str,
+) -> Option<DirSnapshot> {
+  let root = repo.find_commit(commit_id).ok()?.tree().ok()?;
+  let tree_id = if dir.is_empty() {
+    root.id
+  } else {
+    let entry = root.lookup_entry_by_path(dir).ok()??;
+    if !entry.mode().is_tree() {
+      return None;
+    }
+    entry.object_id()
+  };
+
+  let tree = repo.find_tree(tree_id).ok()?;
+  let children = tree
+    .decode()
+    .ok()?
+    .entries
+    .iter()
+    .map(|e| (e.filename.to_owned(), e.oid.to_owned()))
+    .collect();
+  Some((tree_id, children))
+}
+
+/// Last commit that changed each immediate child of `dir` (repo-relative,
+/// empty for the root) as it exists at HEAD, keyed by the child's
+/// repo-relative path: the file browser's "last modified" column.
+///
+/// History is walked once, newest first, and stops when every child has a
+/// commit or after `max_commits` (default 10000) commits; children not
+/// reached by then are left out. Like `git log -1 -- <path>`, merges only
+/// count if they differ from every parent.
+#[napi]
+pub async fn last_commits_for_dir(
+  cwd: String,
+  dir: String,
+  max_commits: Option<u32>,
+) -> napi::Result<HashMap<String, GitLogEntry>> {
+  with_repo(&cwd, |repo| {
+    let head_commit = repo
+      .head_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+    let dir = dir.trim_matches('/').to_string();
+    let Some((_, head_children)) = dir_snapshot(repo, head_commit.id, &dir) else {
+      return Err(napi::Error::from_reason(format!(
+        "Not a directory at HEAD: {dir}"
+      )));
+    };
+
+    let mut pending: HashSet<BString> = head_children.into_keys().collect();
+    let mut result: HashMap<String, GitLogEntry> = HashMap::new();
+    // Parents are visited later in the walk, so keep their snapshots around.
+    let mut snapshots: HashMap<gix::ObjectId, Option<DirSnapshot>> = HashMap::new();
+
+    let iter = repo
+      .rev_walk([head_commit.id])
+      .sorting(gix::revision::walk::Sorting::ByCommitTime(
+        Default::default(),
+      ))
+      .all()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to start rev walk: {e}")))?;
+
+    let max = max_commits.unwrap_or(DEFAULT_DIR_HISTORY_LIMIT) as usize;
+    for commit_info in iter.take(max) {
+      if pending.is_empty() {
+        break;
+      }
+      let info =
+        commit_info.map_err(|e| napi::Error::from_reason(format!("Rev walk error: {e}")))?;
+
+      let snapshot = snapshots
+        .remove(&info.id)
+        .unwrap_or_else(|| dir_snapshot(repo, info.id, &dir));
+      let Some((tree_id, children)) = snapshot else {
+        continue;
+      };
+      for id in &info.parent_ids {
+        snapshots
+          .entry(*id)
+          .or_insert_with(|| dir_snapshot(repo, *id, &dir));
+      }
+      let parents: Vec<Option<&DirSnapshot>> = info
+        .parent_ids
+        .iter()
+        .map(|id| snapshots[id].as_ref())
+        .collect();
+
+      // Directory unchanged relative to a parent: nothing below it changed.
+      if parents
+        .iter()
+        .any(|p| p.is_some_and(|(id, _)| *id == tree_id))
+      {
+        continue;
+      }
+
+      let changed: Vec<BString> = pending
+        .iter()
+        .filter(|name| {
+          let id = children.get(*name);
+          id.is_some()
+            && parents
+              .iter()
+              .all(|p| p.and_then(|(_, parent_children)| parent_children.get(*name)) != id)
+        })
+        .cloned()
+        .collect();
+      if changed.is_empty() {
+        continue;
+      }
+
+      let commit = info
+        .object()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to read commit: {e}")))?;
+      let entry = log_entry(&commit);
+      for name in changed {
+        pending.remove(&name);
+        let path = if dir.is_empty() {
+          name.to_str_lossy().to_string()
+        } else {
+          format!("{}/{}", dir, name.to_str_lossy())
+        };
+        result.insert(path, entry.clone());
+      }
+    }
+
+    Ok(result)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::log_entries;
+  use crate::repo_cache::with_repo;
+  use crate::test_util::{git, git_at, TempDir};
+
+  fn commit(dir: &Path, name: &str, date_secs: u64) -> String {
+    std::fs::write(dir.join(name), name).unwrap();
+    git(dir, &["add", name]);
+    git_at(dir, &["commit", "-q", "-m", name], date_secs);
+    git(dir, &["rev-parse", "HEAD"])
+  }
+
+  #[test]
+  fn topological_order_puts_children_before_parents() {
+    let root = TempDir::repo("topo-log");
+
+    // The side branch was committed on a machine with a skewed clock, so a
+    // walk by commit time (plain `git log`) reaches `base` before `side-1`,
+    // one of its children.
+    let base = commit(&root, "base", 1_800);
+    git(&root, &["checkout", "-q", "-b", "side"]);
+    let side_1 = commit(&root, "side-1", 5_000);
+    let side_2 = commit(&root, "side-2", 1_500);
+    git(&root, &["checkout", "-q", "main"]);
+    let main_1 = commit(&root, "main-1", 2_000);
+    git_at(
+      &root,
+      &["merge", "-q", "--no-ff", "-m", "merge side", "side"],
+      3_000,
+    );
+    let merge = git(&root, &["rev-parse", "HEAD"]);
+
+    let cwd = root.cwd();
+    let hashes: Vec<String> = with_repo(cwd, |repo| log_entries(repo, 20, true))
+      .unwrap()
+      .into_iter()
+      .map(|entry| entry.hash)
+      .collect();
+    let position = |hash: &str| hashes.iter().position(|h| h == hash).unwrap();
+
+    assert_eq!(hashes.len(), 5);
+    assert_eq!(position(&merge), 0);
+    assert!(position(&merge) < position(&main_1));
+    assert!(position(&merge) < position(&side_2));
+    assert!(position(&side_2) < position(&side_1));
+    assert!(position(&side_1) < position(&base));
+    assert!(position(&main_1) < position(&base));
+    assert_eq!(position(&base), 4);
+  }
+}
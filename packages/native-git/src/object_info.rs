@@ -0,0 +1,35 @@
+use crate::file_diff::is_binary;
+use crate::repo_cache::with_repo;
+
+fn resolve_object(repo: &gix::Repository, hash: &str) -> napi::Result<gix::ObjectId> {
+  repo
+    .rev_parse_single(hash)
+    .map(|id| id.detach())
+    .map_err(|e| napi::Error::from_reason(format!("Object not found: {hash} ({e})")))
+}
+
+/// Size in bytes of object `hash` (full or abbreviated), read from the
+/// object header so blob contents are not loaded.
+#[napi]
+pub async fn get_object_size(cwd: String, hash: String) -> napi::Result<u64> {
+  with_repo(&cwd, |repo| {
+    let id = resolve_object(repo, &hash)?;
+    let header = repo
+      .find_header(id)
+      .map_err(|e| napi::Error::from_reason(format!("Object not found: {hash} ({e})")))?;
+    Ok(header.size())
+  })
+}
+
+/// True if blob `hash` looks binary (a NUL byte in its first 8KB), the same
+/// check the status and diff functions use.
+#[napi]
+pub async fn is_binary_blob(cwd: String, hash: String) -> napi::Result<bool> {
+  with_repo(&cwd, |repo| {
+    let id = resolve_object(repo, &hash)?;
+    let blob = repo
+      .find_blob(id)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read blob {hash}: {e}")))?;
+    Ok(is_binary(&blob.data))
+  })
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use crate::repo_cache::with_repo;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct OperationState {
+  /// `"none"`, `"merge"`, `"rebase-merge"`, `"rebase-apply"`,
+  /// `"cherry-pick"`, `"revert"` or `"bisect"`.
+  pub kind: String,
+  /// Commit being merged, cherry-picked or reverted, or the one a rebase
+  /// stopped at.
+  pub target_hash: Option<String>,
+  /// Rebase progress: the 1-based step being applied and the total.
+  pub step: Option<u32>,
+  pub total_steps: Option<u32>,
+}
+
+/// Trimmed first line of `path`, if it exists and isn't empty.
+fn read_first_line(path: &Path) -> Option<String> {
+  let content = std::fs::read_to_string(path).ok()?;
+  let line = content.lines().next()?.trim();
+  (!line.is_empty()).then(|| line.to_string())
+}
+
+fn read_number(path: &Path) -> Option<u32> {
+  read_first_line(path)?.parse().ok()
+}
+
+/// The operation in progress in the worktree at `cwd`, detected like git
+/// does from the state files in its git dir. A rebase takes precedence over
+/// the merge or cherry-pick it may be running for one of its steps.
+#[napi]
+pub async fn get_operation_state(cwd: String) -> napi::Result<OperationState> {
+  let git_dir = with_repo(&cwd, |repo| Ok(repo.git_dir().to_path_buf()))?;
+
+  let state = |kind: &str, target_hash: Option<String>, step: Option<u32>, total: Option<u32>| {
+    OperationState {
+      kind: kind.to_string(),
+      target_hash,
+      step,
+      total_steps: total,
+    }
+  };
+
+  let rebase_merge = git_dir.join("rebase-merge");
+  if rebase_merge.is_dir() {
+    return Ok(state(
+      "rebase-merge",
+      read_first_line(&rebase_merge.join("stopped-sha")),
+      read_number(&rebase_merge.join("msgnum")),
+      read_number(&rebase_merge.join("end")),
+    ));
+  }
+  let rebase_apply = git_dir.join("rebase-apply");
+  if rebase_apply.is_dir() {
+    return Ok(state(
+      "rebase-apply",
+      read_first_line(&rebase_apply.join("original-commit")),
+      read_number(&rebase_apply.join("next")),
+      read_number(&rebase_apply.join("last")),
+    ));
+  }
+
+  let heads = [
+    ("MERGE_HEAD", "merge"),
+    ("CHERRY_PICK_HEAD", "cherry-pick"),
+    ("REVERT_HEAD", "revert"),
+  ];
+  for (file, kind) in heads {
+    if let Some(hash) = read_first_line(&git_dir.join(file)) {
+      return Ok(state(kind, Some(hash), None, None));
+    }
+  }
+
+  if git_dir.join("BISECT_LOG").is_file() {
+    return Ok(state("bisect", None, None, None));
+  }
+
+  Ok(state("none", None, None, None))
+}
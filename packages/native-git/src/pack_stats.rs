@@ -0,0 +1,144 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::repo_cache::with_repo;
+
+/// Loose objects beyond which `needs_gc` is set.
+const GC_LOOSE_OBJECT_THRESHOLD: u32 = 1000;
+/// Pack files beyond which `needs_gc` is set.
+const GC_PACK_THRESHOLD: u32 = 20;
+
+// Output only: napi converts `u64` to a BigInt but not back.
+#[napi(object, object_from_js = false)]
+#[derive(Debug, Clone)]
+pub struct PackStats {
+  pub pack_count: u32,
+  pub loose_object_count: u32,
+  pub total_pack_size_bytes: u64,
+  pub total_loose_size_bytes: u64,
+  /// More than 1000 loose objects or more than 20 packs.
+  pub needs_gc: bool,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CommitGraphInfo {
+  pub exists: bool,
+  pub path: String,
+  /// Commits in the graph, or `None` if the file couldn't be parsed.
+  pub commit_count: Option<u32>,
+}
+
+/// Commit count of a commit-graph file: the last entry of its OID fanout
+/// (`OIDF`) chunk, found through the chunk table following the 8-byte
+/// header.
+fn commit_graph_count(path: &Path) -> std::io::Result<Option<u32>> {
+  let mut file = File::open(path)?;
+  let mut header = [0u8; 8];
+  file.read_exact(&mut header)?;
+  if &header[..4] != b"CGPH" {
+    return Ok(None);
+  }
+
+  // One 12-byte entry (4-byte id, 8-byte offset) per chunk plus a terminator.
+  let chunk_count = header[6] as usize;
+  let mut table = vec![0u8; (chunk_count + 1) * 12];
+  file.read_exact(&mut table)?;
+  let fanout_offset = table.chunks_exact(12).find_map(|entry| {
+    (&entry[..4] == b"OIDF").then(|| u64::from_be_bytes(entry[4..].try_into().unwrap()))
+  });
+  let Some(fanout_offset) = fanout_offset else {
+    return Ok(None);
+  };
+
+  let mut count = [0u8; 4];
+  file.seek(SeekFrom::Start(fanout_offset + 255 * 4))?;
+  file.read_exact(&mut count)?;
+  Ok(Some(u32::from_be_bytes(count)))
+}
+
+/// Count `.pack` files in `objects/pack` and their total size.
+fn pack_stats(objects_dir: &Path) -> (u32, u64) {
+  let Ok(entries) = std::fs::read_dir(objects_dir.join("pack")) else {
+    return (0, 0);
+  };
+  let mut count = 0;
+  let mut size = 0;
+  for entry in entries.flatten() {
+    if entry.path().extension().is_some_and(|ext| ext == "pack") {
+      count += 1;
+      size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+  }
+  (count, size)
+}
+
+/// Count loose objects in the `objects/??/` fan-out directories and their
+/// total size.
+fn loose_stats(objects_dir: &Path) -> (u32, u64) {
+  let Ok(entries) = std::fs::read_dir(objects_dir) else {
+    return (0, 0);
+  };
+  let mut count = 0;
+  let mut size = 0;
+  for dir in entries.flatten() {
+    let name = dir.file_name();
+    let is_fan_out = name
+      .to_str()
+      .is_some_and(|n| n.len() == 2 && n.bytes().all(|b| b.is_ascii_hexdigit()));
+    if !is_fan_out {
+      continue;
+    }
+    let Ok(objects) = std::fs::read_dir(dir.path()) else {
+      continue;
+    };
+    for object in objects.flatten() {
+      count += 1;
+      size += object.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+  }
+  (count, size)
+}
+
+/// Object storage statistics for repo health diagnostics, read straight from
+/// the `objects` directory (shared by all worktrees).
+#[napi]
+pub async fn get_repository_pack_stats(cwd: String) -> napi::Result<PackStats> {
+  let objects_dir = with_repo(&cwd, |repo| Ok(repo.common_dir().join("objects")))?;
+
+  let (pack_count, total_pack_size) = pack_stats(&objects_dir);
+  let (loose_object_count, total_loose_size) = loose_stats(&objects_dir);
+
+  Ok(PackStats {
+    pack_count,
+    loose_object_count,
+    total_pack_size_bytes: total_pack_size,
+    total_loose_size_bytes: total_loose_size,
+    needs_gc: loose_object_count > GC_LOOSE_OBJECT_THRESHOLD || pack_count > GC_PACK_THRESHOLD,
+  })
+}
+
+/// Whether the repository has a commit-graph file
+/// (`objects/info/commit-graph`), which speeds up history walks, and how
+/// many commits it covers. Split graphs (`objects/info/commit-graphs/`) are
+/// not considered.
+#[napi]
+pub async fn get_commit_graph_presence(cwd: String) -> napi::Result<CommitGraphInfo> {
+  let path = with_repo(&cwd, |repo| {
+    Ok(repo.common_dir().join("objects/info/commit-graph"))
+  })?;
+
+  let exists = path.is_file();
+  let commit_count = if exists {
+    commit_graph_count(&path).ok().flatten()
+  } else {
+    None
+  };
+
+  Ok(CommitGraphInfo {
+    exists,
+    path: path.to_string_lossy().to_string(),
+    commit_count,
+  })
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use gix::bstr::ByteSlice;
+use gix::worktree::stack::state::attributes::Source as AttrSource;
+
+use crate::repo_cache::with_repo;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PathAttributes {
+  pub path: String,
+  /// Requested attribute name to its state: `"true"` when set, `"false"` when
+  /// unset (`-attr`), the value for `attr=value`, or `None` when unspecified.
+  pub attributes: HashMap<String, Option<String>>,
+}
+
+/// `.gitattributes` state of `attribute_names` for each of `paths`
+/// (repo-relative), like `git check-attr`. Macros are expanded, so e.g.
+/// `binary` reports `text` and `diff` as unset. Paths don't need to exist.
+#[napi]
+pub async fn get_gitattributes(
+  cwd: String,
+  paths: Vec<String>,
+  attribute_names: Vec<String>,
+) -> napi::Result<Vec<PathAttributes>> {
+  with_repo(&cwd, |repo| {
+    let index = repo
+      .index_or_empty()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+    let attr_handle = repo
+      .attributes_only(&index, AttrSource::WorktreeThenIdMapping)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to load attributes: {e}")))?;
+    let mut outcome =
+      attr_handle.selected_attribute_matches(attribute_names.iter().map(String::as_str));
+    let mut stack = attr_handle.detach();
+
+    let mut result: Vec<PathAttributes> = Vec::with_capacity(paths.len());
+    for path in paths {
+      let mut attributes: HashMap<String, Option<String>> = attribute_names
+        .iter()
+        .map(|name| (name.clone(), None))
+        .collect();
+
+      let platform = stack
+        .at_entry(path.as_bytes().as_bstr(), None, &repo.objects)
+        .map_err(|e| {
+          napi::Error::from_reason(format!("Failed to read attributes for {path}: {e}"))
+        })?;
+      if platform.matching_attributes(&mut outcome) {
+        for m in outcome.iter() {
+          let value = match &m.assignment.state {
+            gix::attrs::StateRef::Set => "true".to_string(),
+            gix::attrs::StateRef::Unset => "false".to_string(),
+            gix::attrs::StateRef::Value(value) => value.as_bstr().to_str_lossy().to_string(),
+            gix::attrs::StateRef::Unspecified => continue,
+          };
+          attributes.insert(m.assignment.name.as_str().to_string(), Some(value));
+        }
+      }
+
+      result.push(PathAttributes { path, attributes });
+    }
+
+    Ok(result)
+  })
+}
@@ -0,0 +1,89 @@
+use gix::bstr::ByteSlice;
+
+/// Normalize like `git check-ref-format --normalize` (trim surrounding
+/// whitespace, drop leading slashes, collapse repeated slashes) and validate
+/// against git's ref name rules.
+fn normalized(name: &str) -> Result<String, String> {
+  let mut normalized = String::with_capacity(name.len());
+  for c in name.trim().trim_start_matches('/').chars() {
+    if c == '/' && normalized.ends_with('/') {
+      continue;
+    }
+    normalized.push(c);
+  }
+
+  gix::validate::reference::name_partial(normalized.as_bytes().as_bstr())
+    .map_err(|e| format!("Invalid ref name '{name}': {e}"))?;
+  Ok(normalized)
+}
+
+/// `name` normalized (surrounding whitespace, leading and repeated slashes
+/// removed) if it's a valid ref name, such as a branch name to pass to
+/// `create_branch`. The error says which rule is violated: no `..`, `@{`,
+/// spaces, control characters or any of `~^:?*[\`, no component starting
+/// with `.` or ending with `.lock`, no trailing `/` or `.`.
+#[napi]
+pub fn normalize_ref_name(name: String) -> napi::Result<String> {
+  normalized(&name).map_err(napi::Error::from_reason)
+}
+
+/// Whether `normalize_ref_name` accepts `name`.
+#[napi]
+pub fn is_valid_ref_name(name: String) -> bool {
+  normalized(&name).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::normalized;
+
+  #[test]
+  fn accepts_and_normalizes_valid_names() {
+    assert_eq!(normalized("main").unwrap(), "main");
+    assert_eq!(
+      normalized("feature/login-form").unwrap(),
+      "feature/login-form"
+    );
+    assert_eq!(normalized("v1.2.3").unwrap(), "v1.2.3");
+    assert_eq!(normalized("  fix/typo \n").unwrap(), "fix/typo");
+    assert_eq!(
+      normalized("/feature//nested///name").unwrap(),
+      "feature/nested/name"
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_names() {
+    let invalid = [
+      "",
+      "   ",
+      "a..b",
+      "branch.lock",
+      "feature/part.lock",
+      "has space",
+      "caret^",
+      "colon:name",
+      "tilde~1",
+      "question?",
+      "star*",
+      "open[bracket",
+      "back\\slash",
+      "reflog@{1}",
+      "trailing/",
+      "trailing.",
+      ".hidden",
+      "feature/.hidden",
+      "bell\u{7}",
+      "delete\u{7f}",
+    ];
+    for name in invalid {
+      assert!(normalized(name).is_err(), "{name:?} should be rejected");
+    }
+  }
+
+  #[test]
+  fn error_names_the_input() {
+    let err = normalized("a..b").unwrap_err();
+    assert!(err.contains("'a..b'"), "{err}");
+  }
+}
@@ -1,4 +1,83 @@
+use crate::checkout::{checkout_tree, current_index, unstaged_paths};
+use crate::diff_summary::staged_changes;
 use crate::repo_cache::{with_repo, evict_repo};
+use crate::status_cache::invalidate_status;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ResetOptions {
+  /// Reset even if tracked files have uncommitted changes. `hard_reset`
+  /// discards them.
+  pub force: Option<bool>,
+}
+
+/// Whether tracked files have staged or unstaged changes. Untracked files
+/// don't count, since a reset leaves them alone.
+fn has_tracked_changes(repo: &gix::Repository) -> napi::Result<bool> {
+  let index = current_index(repo)?;
+  Ok(!staged_changes(repo, &index, None)?.is_empty() || !unstaged_paths(repo)?.is_empty())
+}
+
+/// Commit `target_rev` resolves to, after checking that tracked files are
+/// unchanged unless `force` is set.
+fn reset_target(
+  repo: &gix::Repository,
+  target_rev: &str,
+  options: Option<&ResetOptions>,
+) -> napi::Result<gix::ObjectId> {
+  let commit = repo
+    .rev_parse_single(target_rev)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to parse revision '{target_rev}': {e}")))?
+    .object()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+    .peel_to_commit()
+    .map_err(|e| napi::Error::from_reason(format!("Not a commit: {target_rev} ({e})")))?;
+
+  let force = options.and_then(|o| o.force).unwrap_or(false);
+  if !force && has_tracked_changes(repo)? {
+    return Err(napi::Error::from_reason(
+      "Cannot reset: tracked files have uncommitted changes".to_string(),
+    ));
+  }
+  Ok(commit.id)
+}
+
+/// Point the current branch (or HEAD itself when detached) at `target`,
+/// logging the move as a reset to `target_rev`.
+fn move_head(repo: &gix::Repository, target: gix::ObjectId, target_rev: &str) -> napi::Result<()> {
+  let head = repo
+    .head()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD: {e}")))?;
+
+  // An unborn branch is created; otherwise the ref must still be where
+  // HEAD was when we looked.
+  let previous = match head.id() {
+    Some(id) => gix::refs::transaction::PreviousValue::MustExistAndMatch(
+      gix::refs::Target::Object(id.detach()),
+    ),
+    None => gix::refs::transaction::PreviousValue::MustNotExist,
+  };
+  // Editing HEAD through its symbolic ref moves the branch and logs the
+  // move in both reflogs, as git does.
+  repo
+    .edit_reference(gix::refs::transaction::RefEdit {
+      change: gix::refs::transaction::Change::Update {
+        log: gix::refs::transaction::LogChange {
+          mode: gix::refs::transaction::RefLog::AndReference,
+          force_create_reflog: false,
+          message: format!("reset: moving to {target_rev}").into(),
+        },
+        expected: previous,
+        new: gix::refs::Target::Object(target),
+      },
+      name: "HEAD"
+        .try_into()
+        .map_err(|e| napi::Error::from_reason(format!("Invalid ref name: {e}")))?,
+      deref: true,
+    })
+    .map_err(|e| napi::Error::from_reason(format!("Failed to update HEAD: {e}")))?;
+  Ok(())
+}
 
 #[napi]
 pub async fn reset_soft(cwd: String) -> napi::Result<()> {
@@ -49,6 +128,139 @@ pub async fn reset_soft(cwd: String) -> napi::Result<()> {
 
   // Evict after write regardless of success/failure to ensure fresh state
   evict_repo(&cwd);
+  invalidate_status(&cwd);
 
   result
 }
+
+/// Point the current branch (or HEAD itself when detached) at the commit
+/// `target_rev` resolves to, leaving the index and worktree as they are, so
+/// the difference shows up as staged changes. Fails if tracked files have
+/// uncommitted changes unless `options.force` is set.
+#[napi]
+pub async fn soft_reset(
+  cwd: String,
+  target_rev: String,
+  options: Option<ResetOptions>,
+) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| {
+    let target = reset_target(repo, &target_rev, options.as_ref())?;
+    move_head(repo, target, &target_rev)
+  });
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+fn reset_hard_to(
+  repo: &gix::Repository,
+  target_rev: &str,
+  options: Option<&ResetOptions>,
+) -> napi::Result<()> {
+  let target = reset_target(repo, target_rev, options)?;
+  let tree = repo
+    .find_commit(target)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to find commit: {e}")))?
+    .tree_id()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get commit tree: {e}")))?
+    .detach();
+  // The index and worktree go first, so HEAD only moves once they match.
+  checkout_tree(repo, tree)?;
+  move_head(repo, target, target_rev)
+}
+
+/// Reset the current branch (or detached HEAD), the index and the worktree
+/// to the commit `target_rev` resolves to, like `git reset --hard`. Fails if
+/// tracked files have uncommitted changes unless `options.force` is set, in
+/// which case they are discarded; untracked files are left alone.
+#[napi]
+pub async fn hard_reset(
+  cwd: String,
+  target_rev: String,
+  options: Option<ResetOptions>,
+) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| {
+    reset_hard_to(repo, &target_rev, options.as_ref())
+  });
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{reset_hard_to, ResetOptions};
+  use crate::test_util::{git, TempDir};
+
+  /// A repo on `main` with two commits of `a.txt`: "one" then "two".
+  fn repo_with_history(name: &str) -> TempDir {
+    let dir = TempDir::repo(name);
+    // The reflog entry is written by gix with the repository's identity.
+    git(&dir, &["config", "user.name", "Test"]);
+    git(&dir, &["config", "user.email", "test@example.com"]);
+    std::fs::write(dir.join("a.txt"), "one\n").unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "first"]);
+    std::fs::write(dir.join("a.txt"), "two\n").unwrap();
+    git(&dir, &["commit", "-q", "-am", "second"]);
+    dir
+  }
+
+  fn force() -> Option<ResetOptions> {
+    Some(ResetOptions { force: Some(true) })
+  }
+
+  #[test]
+  fn hard_reset_refuses_tracked_changes() {
+    let dir = repo_with_history("reset-refused");
+    let head = git(&dir, &["rev-parse", "HEAD"]);
+    std::fs::write(dir.join("a.txt"), "edited\n").unwrap();
+
+    let repo = gix::open(&*dir).unwrap();
+    assert!(reset_hard_to(&repo, "HEAD~1", None).is_err());
+    assert_eq!(git(&dir, &["rev-parse", "HEAD"]), head);
+    assert_eq!(
+      std::fs::read_to_string(dir.join("a.txt")).unwrap(),
+      "edited\n"
+    );
+
+    // Staged changes count too.
+    git(&dir, &["add", "a.txt"]);
+    let repo = gix::open(&*dir).unwrap();
+    assert!(reset_hard_to(&repo, "HEAD~1", None).is_err());
+    assert_eq!(git(&dir, &["rev-parse", "HEAD"]), head);
+  }
+
+  #[test]
+  fn forced_hard_reset_discards_tracked_changes() {
+    let dir = repo_with_history("reset-forced");
+    let first = git(&dir, &["rev-parse", "HEAD~1"]);
+    std::fs::write(dir.join("a.txt"), "edited\n").unwrap();
+
+    let repo = gix::open(&*dir).unwrap();
+    reset_hard_to(&repo, "HEAD~1", force().as_ref()).unwrap();
+    assert_eq!(git(&dir, &["rev-parse", "HEAD"]), first);
+    assert_eq!(git(&dir, &["status", "--porcelain"]), "");
+    assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "one\n");
+  }
+
+  #[test]
+  fn hard_reset_ignores_untracked_files() {
+    let dir = repo_with_history("reset-untracked");
+    let first = git(&dir, &["rev-parse", "HEAD~1"]);
+    std::fs::write(dir.join("notes.txt"), "keep me\n").unwrap();
+
+    let repo = gix::open(&*dir).unwrap();
+    reset_hard_to(&repo, "HEAD~1", None).unwrap();
+    assert_eq!(git(&dir, &["rev-parse", "HEAD"]), first);
+    assert_eq!(git(&dir, &["status", "--porcelain"]), "?? notes.txt");
+    assert_eq!(
+      std::fs::read_to_string(dir.join("notes.txt")).unwrap(),
+      "keep me\n"
+    );
+  }
+}
@@ -0,0 +1,266 @@
+use std::io::Read;
+use std::path::Path;
+
+use gix::bstr::ByteSlice;
+use gix::filter::plumbing::driver::apply::{Delay, MaybeDelayed};
+use gix::filter::plumbing::pipeline::convert::ToWorktreeOutcome;
+use gix::object::tree::EntryKind;
+
+use crate::checkout::unstaged_paths;
+use crate::repo_cache::with_repo;
+use crate::status_cache::invalidate_status;
+
+/// Where `restore_paths` takes file content from.
+enum RestoreSource<'repo> {
+  Head(gix::Tree<'repo>),
+  Index,
+}
+
+impl<'repo> RestoreSource<'repo> {
+  fn parse(repo: &'repo gix::Repository, name: Option<&str>) -> napi::Result<Self> {
+    match name.unwrap_or("HEAD") {
+      "HEAD" => {
+        let tree = repo
+          .head_commit()
+          .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?
+          .tree()
+          .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD tree: {e}")))?;
+        Ok(Self::Head(tree))
+      }
+      "index" => Ok(Self::Index),
+      other => Err(napi::Error::from_reason(format!(
+        "Unsupported restore source: {other} (expected \"HEAD\" or \"index\")"
+      ))),
+    }
+  }
+
+  /// Blob id and kind of `path` in this source, or `None` if it's absent.
+  fn entry(
+    &self,
+    index: &gix::index::File,
+    path: &str,
+  ) -> napi::Result<Option<(gix::ObjectId, EntryKind)>> {
+    match self {
+      Self::Head(tree) => {
+        let entry = tree
+          .lookup_entry_by_path(path)
+          .map_err(|e| napi::Error::from_reason(format!("Failed to look up {path}: {e}")))?;
+        Ok(entry.map(|e| (e.object_id(), e.mode().kind())))
+      }
+      Self::Index => Ok(
+        index
+          .entry_by_path_and_stage(
+            path.as_bytes().as_bstr(),
+            gix::index::entry::Stage::Unconflicted,
+          )
+          .and_then(|e| Some((e.id, e.mode.to_tree_entry_mode()?.kind()))),
+      ),
+    }
+  }
+}
+
+/// Blob content as it should appear in the worktree, i.e. after smudge
+/// filters such as `eol` conversion or Git LFS.
+fn worktree_content(
+  pipeline: &mut gix::filter::Pipeline<'_>,
+  data: &[u8],
+  path: &str,
+) -> napi::Result<Vec<u8>> {
+  let outcome = pipeline
+    .convert_to_worktree(data, path.as_bytes().as_bstr(), Delay::Forbid)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to filter {path}: {e}")))?;
+  match outcome {
+    ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => Ok(buf.to_vec()),
+    ToWorktreeOutcome::Process(MaybeDelayed::Immediate(mut read)) => {
+      let mut content = Vec::new();
+      read
+        .read_to_end(&mut content)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to filter {path}: {e}")))?;
+      Ok(content)
+    }
+    ToWorktreeOutcome::Process(MaybeDelayed::Delayed(_)) => Err(napi::Error::from_reason(format!(
+      "Filter for {path} delayed its output"
+    ))),
+  }
+}
+
+#[cfg(unix)]
+fn write_symlink(dest: &Path, target: &[u8]) -> std::io::Result<()> {
+  use std::os::unix::ffi::OsStrExt;
+  std::os::unix::fs::symlink(std::ffi::OsStr::from_bytes(target), dest)
+}
+
+/// Without symlink support the link target is checked out as a plain file,
+/// as git does with `core.symlinks=false`.
+#[cfg(not(unix))]
+fn write_symlink(dest: &Path, target: &[u8]) -> std::io::Result<()> {
+  std::fs::write(dest, target)
+}
+
+#[cfg(unix)]
+fn set_executable(dest: &Path, executable: bool) -> std::io::Result<()> {
+  use std::os::unix::fs::PermissionsExt;
+  let mode = if executable { 0o755 } else { 0o644 };
+  std::fs::set_permissions(dest, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_dest: &Path, _executable: bool) -> std::io::Result<()> {
+  Ok(())
+}
+
+/// Replace whatever is at `dest` with an entry of `kind` holding `content`.
+fn write_entry(dest: &Path, kind: EntryKind, content: &[u8]) -> std::io::Result<()> {
+  if let Some(parent) = dest.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  // Remove first so a symlink is replaced rather than written through, and
+  // a file replaced by a symlink (or vice versa) changes type.
+  if dest.symlink_metadata().is_ok() {
+    std::fs::remove_file(dest)?;
+  }
+
+  if kind == EntryKind::Link {
+    return write_symlink(dest, content);
+  }
+  std::fs::write(dest, content)?;
+  set_executable(dest, kind == EntryKind::BlobExecutable)
+}
+
+/// Write blob `id` to `dest` (the worktree file for `path`) as an entry of
+/// `kind`, with smudge filters applied to regular files.
+pub(crate) fn checkout_blob(
+  repo: &gix::Repository,
+  pipeline: &mut gix::filter::Pipeline<'_>,
+  dest: &Path,
+  path: &str,
+  id: gix::ObjectId,
+  kind: EntryKind,
+) -> napi::Result<()> {
+  let blob = repo
+    .find_object(id)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read blob for {path}: {e}")))?;
+  let content = if kind == EntryKind::Link {
+    blob.data.clone()
+  } else {
+    worktree_content(pipeline, &blob.data, path)?
+  };
+  write_entry(dest, kind, &content)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to write {path}: {e}")))
+}
+
+/// Restore `paths` in the worktree from `source`, returning the ones that
+/// were restored. With `keep_going`, a path that fails to restore is left
+/// out of the result instead of failing the whole call.
+fn restore_from(
+  repo: &gix::Repository,
+  paths: &[String],
+  source: &RestoreSource<'_>,
+  keep_going: bool,
+) -> napi::Result<Vec<String>> {
+  let workdir = repo.workdir().ok_or_else(|| {
+    napi::Error::from_reason("Cannot restore paths in a bare repository".to_string())
+  })?;
+  let index = repo
+    .open_index()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+  let (mut pipeline, _) = repo
+    .filter_pipeline(None)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to set up filters: {e}")))?;
+
+  let mut restored = Vec::new();
+  for path in paths {
+    match restore_path(repo, workdir, source, &index, &mut pipeline, path) {
+      Ok(true) => restored.push(path.clone()),
+      Ok(false) => {}
+      Err(_) if keep_going => {}
+      Err(e) => return Err(e),
+    }
+  }
+  Ok(restored)
+}
+
+/// Restore one path from `source`. Returns `false` if it was skipped.
+fn restore_path(
+  repo: &gix::Repository,
+  workdir: &Path,
+  source: &RestoreSource<'_>,
+  index: &gix::index::File,
+  pipeline: &mut gix::filter::Pipeline<'_>,
+  path: &str,
+) -> napi::Result<bool> {
+  let dest = workdir.join(path);
+  match source.entry(index, path)? {
+    Some((_, EntryKind::Tree | EntryKind::Commit)) => return Ok(false),
+    Some((id, kind)) => checkout_blob(repo, pipeline, &dest, path, id, kind)?,
+    // Without an unconflicted index entry the path is either untracked or
+    // unmerged; neither has a staged version to go back to.
+    None if matches!(source, RestoreSource::Index) => return Ok(false),
+    None => {
+      let tracked = index.entry_by_path(path.as_bytes().as_bstr()).is_some();
+      if !tracked {
+        return Ok(false);
+      }
+      if dest.symlink_metadata().is_ok() {
+        std::fs::remove_file(&dest)
+          .map_err(|e| napi::Error::from_reason(format!("Failed to delete {path}: {e}")))?;
+      }
+    }
+  }
+  Ok(true)
+}
+
+/// Discard worktree changes to `paths` (repo-relative), like
+/// `git restore [--source=HEAD] -- <paths>`.
+///
+/// `source` is `"HEAD"` (default) or `"index"` for the staged version. Files
+/// are rewritten with the source's content and mode (smudge filters applied),
+/// and files the source doesn't have are deleted. The index is left as is.
+/// Paths that are neither in the index nor in the source, as well as
+/// directories, submodules and (from the index) unmerged paths, are skipped.
+/// Returns the number of paths restored.
+#[napi]
+pub async fn restore_paths(
+  cwd: String,
+  paths: Vec<String>,
+  source: Option<String>,
+) -> napi::Result<u32> {
+  let result = with_repo(&cwd, |repo| {
+    let source = RestoreSource::parse(repo, source.as_deref())?;
+    let restored = restore_from(repo, &paths, &source, false)?;
+    Ok(restored.len() as u32)
+  });
+
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Discard unstaged changes to `paths` (repo-relative) by writing back their
+/// index version, like `git restore -- <paths>`. Deleted files are
+/// re-created. Untracked and unmerged paths, directories and submodules are
+/// skipped, and nothing is deleted. A path that fails to restore doesn't
+/// stop the others. Returns the paths restored.
+#[napi]
+pub async fn discard_changes(cwd: String, paths: Vec<String>) -> napi::Result<Vec<String>> {
+  let result = with_repo(&cwd, |repo| {
+    restore_from(repo, &paths, &RestoreSource::Index, true)
+  });
+
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// `discard_changes` for every tracked file that differs from the index.
+#[napi]
+pub async fn discard_all_changes(cwd: String) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| {
+    let paths: Vec<String> = unstaged_paths(repo)?.into_iter().collect();
+    restore_from(repo, &paths, &RestoreSource::Index, true).map(|_| ())
+  });
+
+  invalidate_status(&cwd);
+
+  result
+}
@@ -0,0 +1,277 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::repo_cache::with_repo;
+
+/// Commit headers that carry a signature (`gpgsig-sha256` in SHA-256 repos).
+const SIGNATURE_HEADERS: [&[u8]; 2] = [b"gpgsig", b"gpgsig-sha256"];
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SignatureStatus {
+  pub is_signed: bool,
+  /// `None` when the signature couldn't be checked: the verification tool
+  /// isn't installed, the signer's key is unknown, or the format isn't
+  /// supported.
+  pub is_valid: Option<bool>,
+  /// GPG key fingerprint (or key id) or SSH key fingerprint.
+  pub signer_key: Option<String>,
+  /// `"gpg"` or `"ssh"`.
+  pub method: Option<String>,
+}
+
+/// Split a raw commit object into its signature and the signed payload (the
+/// commit with its signature headers removed), or `None` if it's unsigned.
+fn split_signature(raw: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+  let mut signature: Option<Vec<u8>> = None;
+  let mut payload = Vec::with_capacity(raw.len());
+  let mut lines = raw.split_inclusive(|&b| b == b'\n').peekable();
+
+  while let Some(line) = lines.next() {
+    if line == b"\n" {
+      // End of the headers; the rest is the message.
+      payload.extend_from_slice(line);
+      for rest in lines.by_ref() {
+        payload.extend_from_slice(rest);
+      }
+      break;
+    }
+
+    let value = SIGNATURE_HEADERS.iter().find_map(|name| {
+      line
+        .strip_prefix(*name)
+        .and_then(|rest| rest.strip_prefix(b" "))
+    });
+    let Some(value) = value else {
+      payload.extend_from_slice(line);
+      continue;
+    };
+
+    // Multi-line header values continue on lines starting with a space.
+    let mut value = value.to_vec();
+    while let Some(cont) = lines.next_if(|l| l.starts_with(b" ")) {
+      value.extend_from_slice(&cont[1..]);
+    }
+    signature.get_or_insert(value);
+  }
+
+  signature.map(|s| (s, payload))
+}
+
+/// Temporary file removed on drop.
+struct TempFile(PathBuf);
+
+/// Keeps temp file names unique across concurrent verifications.
+static TEMP_FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+impl TempFile {
+  fn new(name: &str, content: &[u8]) -> std::io::Result<Self> {
+    let n = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("native-git-{}-{n}-{name}", std::process::id()));
+    std::fs::write(&path, content)?;
+    Ok(Self(path))
+  }
+
+  fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.0);
+  }
+}
+
+/// Verify with `gpg --verify`, reading its machine-readable status lines.
+fn verify_gpg(signature: &TempFile, payload: &TempFile) -> (Option<bool>, Option<String>) {
+  let output = match Command::new("gpg")
+    .args(["--status-fd=1", "--verify"])
+    .arg(signature.path())
+    .arg(payload.path())
+    .stdin(Stdio::null())
+    .stderr(Stdio::null())
+    .output()
+  {
+    Ok(output) => output,
+    Err(_) => return (None, None),
+  };
+
+  let mut is_valid = None;
+  let mut key_id = None;
+  let mut fingerprint = None;
+  for line in String::from_utf8_lossy(&output.stdout).lines() {
+    let Some(status) = line.strip_prefix("[GNUPG:] ") else {
+      continue;
+    };
+    let mut fields = status.split_whitespace();
+    let keyword = fields.next().unwrap_or_default();
+    let key = fields.next().map(str::to_string);
+    match keyword {
+      "GOODSIG" => (is_valid, key_id) = (Some(true), key),
+      "BADSIG" | "EXPKEYSIG" | "REVKEYSIG" => (is_valid, key_id) = (Some(false), key),
+      // Usually a missing public key: the signature can't be checked.
+      "ERRSIG" => key_id = key,
+      "VALIDSIG" => fingerprint = key,
+      _ => {}
+    }
+  }
+  (is_valid, fingerprint.or(key_id))
+}
+
+/// Verify with `ssh-keygen -Y check-novalidate` against the public key
+/// embedded in the signature. This proves the commit was signed by that key;
+/// whether the key is trusted (git's `gpg.ssh.allowedSignersFile`) is up to
+/// the caller.
+fn verify_ssh(signature: &TempFile, payload: &TempFile) -> (Option<bool>, Option<String>) {
+  let Ok(stdin) = std::fs::File::open(payload.path()) else {
+    return (None, None);
+  };
+  let output = match Command::new("ssh-keygen")
+    .args(["-Y", "check-novalidate", "-n", "git", "-s"])
+    .arg(signature.path())
+    .stdin(stdin)
+    .stderr(Stdio::null())
+    .output()
+  {
+    Ok(output) => output,
+    Err(_) => return (None, None),
+  };
+
+  // e.g. `Good "git" signature with ED25519 key SHA256:...`
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let signer_key = stdout.lines().find_map(|line| {
+    line
+      .split_once(" key ")
+      .map(|(_, key)| key.trim().to_string())
+  });
+  (Some(output.status.success()), signer_key)
+}
+
+/// Check whether commit `hash` is signed and, if the matching tool (`gpg` or
+/// `ssh-keygen`) is installed, whether the signature verifies. SSH
+/// signatures are checked against the key embedded in the signature, without
+/// consulting an allowed-signers list.
+#[napi]
+pub async fn verify_commit_signature(cwd: String, hash: String) -> napi::Result<SignatureStatus> {
+  let (id, raw) = with_repo(&cwd, |repo| {
+    let commit = repo
+      .rev_parse_single(hash.as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to parse revision '{hash}': {e}")))?
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+      .try_into_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Object is not a commit: {e}")))?;
+    Ok((commit.id.to_string(), commit.data.clone()))
+  })?;
+
+  let Some((signature, payload)) = split_signature(&raw) else {
+    return Ok(SignatureStatus {
+      is_signed: false,
+      is_valid: None,
+      signer_key: None,
+      method: None,
+    });
+  };
+
+  let method = if signature.starts_with(b"-----BEGIN PGP SIGNATURE-----") {
+    Some("gpg")
+  } else if signature.starts_with(b"-----BEGIN SSH SIGNATURE-----") {
+    Some("ssh")
+  } else {
+    None
+  };
+
+  let (is_valid, signer_key) = match method {
+    Some(method) => {
+      let files = TempFile::new(&format!("{id}.sig"), &signature)
+        .and_then(|sig| Ok((sig, TempFile::new(&format!("{id}.payload"), &payload)?)));
+      match files {
+        Ok((sig, payload)) if method == "gpg" => verify_gpg(&sig, &payload),
+        Ok((sig, payload)) => verify_ssh(&sig, &payload),
+        Err(_) => (None, None),
+      }
+    }
+    None => (None, None),
+  };
+
+  Ok(SignatureStatus {
+    is_signed: true,
+    is_valid,
+    signer_key,
+    method: method.map(str::to_string),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::split_signature;
+
+  const UNSIGNED: &[u8] = concat!(
+    "tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n",
+    "author Test <test@example.com> 1700000000 +0000\n",
+    "committer Test <test@example.com> 1700000000 +0000\n",
+    "\n",
+    "Subject\n",
+    "\n",
+    " indented body line\n",
+  )
+  .as_bytes();
+
+  /// `UNSIGNED` with `header` and its continuation lines inserted after the
+  /// committer line, as git writes it.
+  fn signed(header: &str) -> Vec<u8> {
+    let text = std::str::from_utf8(UNSIGNED).unwrap();
+    let (headers, message) = text.split_once("\n\n").unwrap();
+    format!("{headers}\n{header}\n\n{message}").into_bytes()
+  }
+
+  #[test]
+  fn splits_gpgsig_header() {
+    let raw = signed(
+      "gpgsig -----BEGIN PGP SIGNATURE-----\n \n iQEzBAABCAAd\n -----END PGP SIGNATURE-----",
+    );
+    let (signature, payload) = split_signature(&raw).unwrap();
+    assert_eq!(
+      signature,
+      b"-----BEGIN PGP SIGNATURE-----\n\niQEzBAABCAAd\n-----END PGP SIGNATURE-----\n"
+    );
+    assert_eq!(payload, UNSIGNED);
+  }
+
+  #[test]
+  fn splits_gpgsig_sha256_header() {
+    let raw = signed(
+      "gpgsig-sha256 -----BEGIN SSH SIGNATURE-----\n U1NIU0lH\n -----END SSH SIGNATURE-----",
+    );
+    let (signature, payload) = split_signature(&raw).unwrap();
+    assert_eq!(
+      signature,
+      b"-----BEGIN SSH SIGNATURE-----\nU1NIU0lH\n-----END SSH SIGNATURE-----\n"
+    );
+    assert_eq!(payload, UNSIGNED);
+  }
+
+  #[test]
+  fn unsigned_commit_has_no_signature() {
+    assert!(split_signature(UNSIGNED).is_none());
+  }
+
+  #[test]
+  fn continuation_lines_end_at_the_next_header() {
+    // The signature's continuation lines stop at `mergetag`, whose own
+    // continuation lines stay in the payload, as do indented message lines.
+    let raw = signed(concat!(
+      "gpgsig -----BEGIN PGP SIGNATURE-----\n line one\n line two\n -----END PGP SIGNATURE-----\n",
+      "mergetag object 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n type commit",
+    ));
+    let (signature, payload) = split_signature(&raw).unwrap();
+    assert_eq!(
+      signature,
+      b"-----BEGIN PGP SIGNATURE-----\nline one\nline two\n-----END PGP SIGNATURE-----\n"
+    );
+    let expected = signed("mergetag object 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n type commit");
+    assert_eq!(payload, expected);
+  }
+}
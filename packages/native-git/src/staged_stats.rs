@@ -0,0 +1,82 @@
+use crate::attributes::{counting_attributes, path_attrs, PathAttrs};
+use crate::diff_summary::staged_changes;
+use crate::file_diff::is_binary;
+use crate::repo_cache::with_repo;
+use crate::status_summary::count_line_changes;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FileDiffStat {
+  pub path: String,
+  pub status: String,
+  pub added: u32,
+  pub deleted: u32,
+  pub binary: bool,
+  /// Tracked with Git LFS (`filter=lfs`); counted as binary.
+  pub lfs: bool,
+}
+
+pub(crate) fn read_blob(repo: &gix::Repository, id: Option<&gix::ObjectId>) -> Vec<u8> {
+  id.and_then(|id| repo.find_object(*id).ok())
+    .map(|o| o.detach().data)
+    .unwrap_or_default()
+}
+
+/// Per-file line counts for the staged changes (HEAD tree vs index), the
+/// numbers `git diff --cached --numstat` reports. Binary files are flagged
+/// with zero counts, as are Git LFS files (also flagged `lfs`); unmerged paths
+/// are reported as `conflicted`.
+#[napi]
+pub async fn get_staged_diff_stats(cwd: String) -> napi::Result<Vec<FileDiffStat>> {
+  with_repo(&cwd, |repo| {
+    let index = repo
+      .open_index()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
+
+    let mut counting_attrs = counting_attributes(repo, &index);
+    let mut attrs_for = |path: &str| match counting_attrs {
+      Some((ref mut stack, ref mut outcome)) => path_attrs(stack, outcome, path, &repo.objects),
+      None => PathAttrs::default(),
+    };
+
+    let mut stats: Vec<FileDiffStat> = Vec::new();
+    for change in staged_changes(repo, &index, None)? {
+      if change.status == "conflicted" {
+        stats.push(FileDiffStat {
+          path: change.path,
+          status: change.status.to_string(),
+          added: 0,
+          deleted: 0,
+          binary: false,
+          lfs: false,
+        });
+        continue;
+      }
+      // Gitlinks point at commits in another repo and symlinks have no
+      // lines, so neither is counted.
+      if change.mode.is_submodule() || change.mode == gix::index::entry::Mode::SYMLINK {
+        continue;
+      }
+
+      let attrs = attrs_for(&change.path);
+      let old = read_blob(repo, change.head_id.as_ref());
+      let new = read_blob(repo, change.index_id.as_ref());
+      let binary = attrs.skip_line_count() || is_binary(&old) || is_binary(&new);
+      let (added, deleted) = if binary {
+        (0, 0)
+      } else {
+        count_line_changes(&old, &new, gix::diff::blob::Algorithm::Histogram)
+      };
+
+      stats.push(FileDiffStat {
+        path: change.path,
+        status: change.status.to_string(),
+        added,
+        deleted,
+        binary,
+        lfs: attrs.lfs,
+      });
+    }
+    Ok(stats)
+  })
+}
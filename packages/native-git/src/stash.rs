@@ -0,0 +1,689 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use gix::bstr::{BString, ByteSlice};
+use gix::merge::blob::builtin_driver::text::Labels;
+use gix::merge::tree::apply_index_entries::RemovalMode;
+use gix::merge::tree::TreatAsUnresolved;
+use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+
+use crate::checkout::{
+  changed_paths, checkout_tree, current_index, has_unmerged, remove_worktree_file, tree_paths,
+  unstaged_paths, write_index, write_index_tree, write_paths, write_worktree_tree,
+};
+use crate::diff_summary::{DiffSummaryResult, FileDiffSummaryItem};
+use crate::repo_cache::{evict_repo, with_repo};
+use crate::status_cache::invalidate_status;
+use crate::status_summary::{is_excluded, shared_excludes};
+
+const STASH_REF: &str = "refs/stash";
+
+/// Untracked files in the worktree, as `git stash -u` collects them:
+/// ignored files (including those in the shared `info/exclude`) are left
+/// out.
+fn untracked_paths(repo: &gix::Repository) -> napi::Result<BTreeSet<String>> {
+  let status_iter = repo
+    .status(gix::progress::Discard)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to create status: {e}")))?
+    .untracked_files(gix::status::UntrackedFiles::Files)
+    .into_index_worktree_iter(Vec::<BString>::new())
+    .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
+
+  let excludes = shared_excludes(repo);
+  let mut paths = BTreeSet::new();
+  for entry in status_iter {
+    let entry =
+      entry.map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
+    let gix::status::index_worktree::Item::DirectoryContents { entry: dir, .. } = &entry else {
+      continue;
+    };
+    let rela_path = dir.rela_path.to_string();
+    if excludes
+      .as_ref()
+      .is_some_and(|search| is_excluded(search, &rela_path))
+    {
+      continue;
+    }
+    paths.insert(rela_path);
+  }
+  Ok(paths)
+}
+
+/// Point `refs/stash` at `id`, appending `message` to its reflog (which is
+/// created if needed, as git always keeps one for the stash).
+fn update_stash_ref(
+  repo: &gix::Repository,
+  id: gix::ObjectId,
+  expected: PreviousValue,
+  message: &str,
+) -> napi::Result<()> {
+  repo
+    .edit_reference(RefEdit {
+      change: Change::Update {
+        log: LogChange {
+          mode: RefLog::AndReference,
+          force_create_reflog: true,
+          message: message.into(),
+        },
+        expected,
+        new: gix::refs::Target::Object(id),
+      },
+      name: STASH_REF
+        .try_into()
+        .map_err(|e| napi::Error::from_reason(format!("Invalid ref name: {e}")))?,
+      deref: false,
+    })
+    .map_err(|e| napi::Error::from_reason(format!("Failed to update {STASH_REF}: {e}")))?;
+  Ok(())
+}
+
+fn push_stash(
+  repo: &gix::Repository,
+  message: Option<&str>,
+  include_untracked: bool,
+) -> napi::Result<String> {
+  let head = repo.head_commit().map_err(|e| {
+    napi::Error::from_reason(format!("Cannot stash without an initial commit: {e}"))
+  })?;
+  let head_tree = head
+    .tree_id()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD tree: {e}")))?
+    .detach();
+  let index = current_index(repo)?;
+  if has_unmerged(&index) {
+    return Err(napi::Error::from_reason(
+      "Cannot stash: the index has unmerged paths".to_string(),
+    ));
+  }
+
+  let index_tree = write_index_tree(repo, &index)?;
+  let unstaged = unstaged_paths(repo)?;
+  let untracked = if include_untracked {
+    untracked_paths(repo)?
+  } else {
+    BTreeSet::new()
+  };
+  if index_tree == head_tree && unstaged.is_empty() && untracked.is_empty() {
+    return Err(napi::Error::from_reason(
+      "Nothing to stash: the worktree is clean".to_string(),
+    ));
+  }
+
+  // Same commits and messages as `git stash push`: the index state on top
+  // of HEAD, then the worktree state with HEAD, the index commit and the
+  // untracked files (a root commit) as parents.
+  let branch = repo
+    .head_name()
+    .ok()
+    .flatten()
+    .map(|name| name.shorten().to_string())
+    .unwrap_or_else(|| "(no branch)".to_string());
+  let subject = head
+    .message()
+    .map(|message| message.summary().to_string())
+    .unwrap_or_default();
+  let on = format!("{branch}: {} {subject}", head.id().shorten_or_id());
+  let commit = |message: String, tree: gix::ObjectId, parents: Vec<gix::ObjectId>| {
+    repo
+      .new_commit(message, tree, parents)
+      .map(|commit| commit.id)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to write stash commit: {e}")))
+  };
+
+  let index_commit = commit(format!("index on {on}\n"), index_tree, vec![head.id])?;
+  let mut parents = vec![head.id, index_commit];
+  if !untracked.is_empty() {
+    let empty_tree = gix::ObjectId::empty_tree(repo.object_hash());
+    let untracked_tree = write_worktree_tree(repo, &index, empty_tree, &untracked)?;
+    parents.push(commit(
+      format!("untracked files on {on}\n"),
+      untracked_tree,
+      Vec::new(),
+    )?);
+  }
+  let worktree_tree = write_worktree_tree(repo, &index, index_tree, &unstaged)?;
+  let title = match message {
+    Some(message) => format!("On {branch}: {message}"),
+    None => format!("WIP on {on}"),
+  };
+  let stash = commit(format!("{title}\n"), worktree_tree, parents)?;
+  update_stash_ref(repo, stash, PreviousValue::Any, &title)?;
+
+  checkout_tree(repo, head_tree)?;
+  if let Some(workdir) = repo.workdir() {
+    for path in &untracked {
+      remove_worktree_file(workdir, path)?;
+    }
+  }
+  Ok(stash.to_string())
+}
+
+/// Stash the worktree and index changes, like `git stash push`.
+///
+/// Records the stash commit under `refs/stash` (with the usual
+/// `On <branch>: <message>` reflog entry, or `WIP on <branch>: ...` without a
+/// message) and resets the worktree and index to HEAD. With
+/// `include_untracked`, untracked files are stashed and removed too. Returns
+/// the new stash commit hash; fails if there is nothing to stash.
+#[napi]
+pub async fn create_stash(
+  cwd: String,
+  message: Option<String>,
+  include_untracked: Option<bool>,
+) -> napi::Result<String> {
+  let result = with_repo(&cwd, |repo| {
+    push_stash(repo, message.as_deref(), include_untracked.unwrap_or(false))
+  });
+
+  // Evict after write regardless of success/failure to ensure fresh state
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Apply stash entry `index` on top of the current index and worktree. See
+/// [`apply_stash`].
+fn apply_stash_entry(repo: &gix::Repository, index: u32) -> napi::Result<()> {
+  let stash_id = stash_commit_id(repo, index)?;
+  let stash = repo
+    .find_commit(stash_id)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to find stash commit: {e}")))?;
+  let parents: Vec<gix::ObjectId> = stash.parent_ids().map(|id| id.detach()).collect();
+  let (Some(&base), Some(&index_commit)) = (parents.first(), parents.get(1)) else {
+    return Err(napi::Error::from_reason(format!(
+      "stash@{{{index}}} is not a stash commit"
+    )));
+  };
+  let base_tree = commit_tree(repo, base)?.id;
+  let stash_tree = commit_tree(repo, stash_id)?.id;
+  let stashed_index_tree = commit_tree(repo, index_commit)?.id;
+  let untracked_tree = match parents.get(2) {
+    Some(&id) => Some(commit_tree(repo, id)?.id),
+    None => None,
+  };
+
+  let current = current_index(repo)?;
+  if has_unmerged(&current) {
+    return Err(napi::Error::from_reason(
+      "Cannot apply a stash: the index has unmerged paths".to_string(),
+    ));
+  }
+  let ours_tree = write_index_tree(repo, &current)?;
+
+  let merge_err =
+    |e: &dyn std::fmt::Display| napi::Error::from_reason(format!("Failed to merge stash: {e}"));
+  let options = repo
+    .tree_merge_options()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read merge options: {e}")))?;
+  let labels = Labels {
+    ancestor: Some("Stash base".into()),
+    current: Some("Updated upstream".into()),
+    other: Some("Stashed changes".into()),
+  };
+  let how = TreatAsUnresolved::default();
+  let mut merged = repo
+    .merge_trees(base_tree, ours_tree, stash_tree, labels, options.clone())
+    .map_err(|e| merge_err(&e))?;
+  let conflicted = merged.has_unresolved_conflicts(how);
+  let merged_tree = merged.tree.write().map_err(|e| merge_err(&e))?.detach();
+
+  // As with `git stash apply --index`, staged changes are only restored
+  // when the stash merged cleanly, and must merge cleanly themselves.
+  let staged_tree = if conflicted || stashed_index_tree == base_tree {
+    None
+  } else {
+    let mut staged = repo
+      .merge_trees(base_tree, ours_tree, stashed_index_tree, labels, options)
+      .map_err(|e| merge_err(&e))?;
+    if staged.has_unresolved_conflicts(how) {
+      return Err(napi::Error::from_reason(format!(
+        "Conflicts in the staged changes of stash@{{{index}}}; nothing was applied"
+      )));
+    }
+    Some(staged.tree.write().map_err(|e| merge_err(&e))?.detach())
+  };
+
+  let workdir = repo.workdir().ok_or_else(|| {
+    napi::Error::from_reason("Cannot apply a stash in a bare repository".to_string())
+  })?;
+  let changed = changed_paths(repo, ours_tree, merged_tree)?;
+  let unstaged = unstaged_paths(repo)?;
+  let untracked = match untracked_tree {
+    Some(tree) => tree_paths(repo, tree)?,
+    None => BTreeSet::new(),
+  };
+  // Nothing is touched if local changes or untracked files would be lost.
+  let overwritten: Vec<&str> = changed
+    .iter()
+    .filter(|path| {
+      unstaged.contains(*path)
+        || (current.entry_by_path(path.as_bytes().as_bstr()).is_none()
+          && workdir.join(path).symlink_metadata().is_ok())
+    })
+    .chain(
+      untracked
+        .iter()
+        .filter(|path| workdir.join(path).symlink_metadata().is_ok()),
+    )
+    .map(String::as_str)
+    .collect();
+  if !overwritten.is_empty() {
+    return Err(napi::Error::from_reason(format!(
+      "Your local changes to these files would be overwritten: {}",
+      overwritten.join(", ")
+    )));
+  }
+
+  let written = write_paths(repo, merged_tree, &changed)?;
+  if let Some(tree) = untracked_tree {
+    write_paths(repo, tree, &untracked)?;
+  }
+
+  let index_err =
+    |e: &dyn std::fmt::Display| napi::Error::from_reason(format!("Failed to update index: {e}"));
+  let new_index = if conflicted {
+    let mut new_index = repo
+      .index_from_tree(&merged_tree)
+      .map_err(|e| index_err(&e))?;
+    merged.index_changed_after_applying_conflicts(&mut new_index, how, RemovalMode::Prune);
+    new_index
+  } else {
+    let tree = match staged_tree {
+      Some(tree) => tree,
+      // Without staged changes everything stays unstaged, except files the
+      // stash adds: git keeps those in the index so they aren't untracked.
+      None => {
+        let ours = repo.find_tree(ours_tree).map_err(|e| index_err(&e))?;
+        let merged = repo.find_tree(merged_tree).map_err(|e| index_err(&e))?;
+        let mut editor = repo.edit_tree(ours_tree).map_err(|e| index_err(&e))?;
+        for path in &changed {
+          if ours
+            .lookup_entry_by_path(path)
+            .map_err(|e| index_err(&e))?
+            .is_some()
+          {
+            continue;
+          }
+          if let Some(entry) = merged
+            .lookup_entry_by_path(path)
+            .map_err(|e| index_err(&e))?
+          {
+            editor
+              .upsert(path, entry.mode().kind(), entry.object_id())
+              .map_err(|e| index_err(&e))?;
+          }
+        }
+        editor.write().map_err(|e| index_err(&e))?.detach()
+      }
+    };
+    repo.index_from_tree(&tree).map_err(|e| index_err(&e))?
+  };
+  write_index(repo, new_index, &current, &written)?;
+
+  if conflicted {
+    let paths: BTreeSet<String> = merged
+      .conflicts
+      .iter()
+      .filter(|conflict| conflict.is_unresolved(how))
+      .flat_map(|conflict| [conflict.ours.location(), conflict.theirs.location()])
+      .map(|location| location.to_string())
+      .collect();
+    return Err(napi::Error::from_reason(format!(
+      "Conflicts applying stash@{{{index}}} in: {}. The stash entry is kept.",
+      paths.into_iter().collect::<Vec<_>>().join(", ")
+    )));
+  }
+  Ok(())
+}
+
+/// Apply `stash@{index}` to the worktree, restoring its staged changes to
+/// the index as well, like `git stash apply --index`. With `pop`, the entry
+/// is dropped afterwards.
+///
+/// Stashed changes that clash with local ones are merged: conflicting paths
+/// are left as unmerged index entries with conflict markers, an error is
+/// returned, and the entry is kept even with `pop`. Nothing is touched when
+/// the stash would overwrite uncommitted changes or untracked files.
+#[napi]
+pub async fn apply_stash(cwd: String, index: u32, pop: Option<bool>) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| {
+    apply_stash_entry(repo, index)?;
+    if pop.unwrap_or(false) {
+      drop_stash_entry(repo, index)?;
+    }
+    Ok(())
+  });
+
+  // Evict after write regardless of success/failure to ensure fresh state
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Remove entry `index` from the stash reflog, like
+/// `git reflog delete --rewrite --updateref`: the next newer entry takes
+/// over the removed one's previous id, `refs/stash` follows the newest
+/// remaining entry, and the ref is deleted with the last one.
+fn drop_stash_entry(repo: &gix::Repository, index: u32) -> napi::Result<()> {
+  let no_entry = || napi::Error::from_reason(format!("No stash entry stash@{{{index}}}"));
+  let log_err = |e: &dyn std::fmt::Display| {
+    napi::Error::from_reason(format!("Failed to rewrite stash reflog: {e}"))
+  };
+  let current = repo
+    .find_reference(STASH_REF)
+    .map_err(|_| no_entry())?
+    .id()
+    .detach();
+  let log_path = repo.common_dir().join("logs").join(STASH_REF);
+  let log = std::fs::read(&log_path).map_err(|_| no_entry())?;
+
+  let mut lines: Vec<Vec<u8>> = log
+    .lines()
+    .filter(|line| !line.is_empty())
+    .map(<[u8]>::to_vec)
+    .collect();
+  let position = lines
+    .len()
+    .checked_sub(index as usize + 1)
+    .ok_or_else(no_entry)?;
+  let dropped = lines.remove(position);
+  // Each line starts with `<old id> <new id> `.
+  let field = |line: &[u8], n: usize| -> napi::Result<gix::ObjectId> {
+    let hex = line
+      .split_str(" ")
+      .nth(n)
+      .ok_or_else(|| log_err(&"malformed entry"))?;
+    gix::ObjectId::from_hex(hex).map_err(|e| log_err(&e))
+  };
+  if let Some(next) = lines.get_mut(position) {
+    let old_len = field(next, 0)?.to_string().len();
+    let dropped_old = field(&dropped, 0)?.to_string();
+    next.splice(..old_len, dropped_old.into_bytes());
+  }
+
+  let name: gix::refs::FullName = STASH_REF
+    .try_into()
+    .map_err(|e| napi::Error::from_reason(format!("Invalid ref name: {e}")))?;
+  let Some(newest) = lines.last() else {
+    repo
+      .edit_reference(RefEdit {
+        change: Change::Delete {
+          expected: PreviousValue::MustExistAndMatch(gix::refs::Target::Object(current)),
+          log: RefLog::AndReference,
+        },
+        name,
+        deref: false,
+      })
+      .map_err(|e| napi::Error::from_reason(format!("Failed to delete {STASH_REF}: {e}")))?;
+    return Ok(());
+  };
+  let newest_id = field(newest, 1)?;
+  if newest_id != current {
+    // The reflog entry this appends is overwritten below.
+    update_stash_ref(
+      repo,
+      newest_id,
+      PreviousValue::MustExistAndMatch(gix::refs::Target::Object(current)),
+      "drop",
+    )?;
+  }
+
+  let mut file = gix::lock::File::acquire_to_update_resource(
+    &log_path,
+    gix::lock::acquire::Fail::Immediately,
+    None,
+  )
+  .map_err(|e| log_err(&e))?;
+  for line in &lines {
+    file.write_all(line).map_err(|e| log_err(&e))?;
+    file.write_all(b"\n").map_err(|e| log_err(&e))?;
+  }
+  file.commit().map_err(|e| log_err(&e.error))?;
+  Ok(())
+}
+
+/// Remove `stash@{index}`. The stash reflog is rewritten without the entry,
+/// so older entries move up one index.
+#[napi]
+pub async fn drop_stash(cwd: String, index: u32) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| drop_stash_entry(repo, index));
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Commit recorded as `stash@{index}`: entry `index` of the `refs/stash`
+/// reflog, counting from the newest.
+fn stash_commit_id(repo: &gix::Repository, index: u32) -> napi::Result<gix::ObjectId> {
+  let no_entry = || napi::Error::from_reason(format!("No stash entry stash@{{{index}}}"));
+  let reference = repo.find_reference(STASH_REF).map_err(|_| no_entry())?;
+  let mut log = reference.log_iter();
+  let line = log
+    .rev()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read stash reflog: {e}")))?
+    .ok_or_else(no_entry)?
+    .nth(index as usize)
+    .ok_or_else(no_entry)?
+    .map_err(|e| napi::Error::from_reason(format!("Failed to read stash reflog: {e}")))?;
+  Ok(line.new_oid)
+}
+
+fn commit_tree<'repo>(
+  repo: &'repo gix::Repository,
+  id: gix::ObjectId,
+) -> napi::Result<gix::Tree<'repo>> {
+  repo
+    .find_commit(id)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to find commit: {e}")))?
+    .tree()
+    .map_err(|e| napi::Error::from_reason(format!("Failed to get commit tree: {e}")))
+}
+
+/// Files changed by `stash@{stash_index}`: the stash commit compared
+/// against its first parent (the commit the stash was made on). Files from
+/// the untracked-files parent (`git stash -u`) are included as `added`.
+#[napi]
+pub async fn get_stash_diff(cwd: String, stash_index: u32) -> napi::Result<DiffSummaryResult> {
+  with_repo(&cwd, |repo| {
+    let stash_id = stash_commit_id(repo, stash_index)?;
+    let stash = repo
+      .find_commit(stash_id)
+      .map_err(|e| napi::Error::from_reason(format!("Failed to find stash commit: {e}")))?;
+    let parents: Vec<gix::ObjectId> = stash.parent_ids().map(|id| id.detach()).collect();
+    let base_id = parents
+      .first()
+      .copied()
+      .ok_or_else(|| napi::Error::from_reason("Stash commit has no base parent".to_string()))?;
+
+    let base_tree = commit_tree(repo, base_id)?;
+    let stash_tree = commit_tree(repo, stash_id)?;
+    let changes = repo
+      .diff_tree_to_tree(&base_tree, &stash_tree, gix::diff::Options::default())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to diff trees: {e}")))?;
+
+    use gix::object::tree::diff::ChangeDetached;
+    let mut files: Vec<FileDiffSummaryItem> = Vec::new();
+    for change in changes {
+      let (location, status) = match change {
+        ChangeDetached::Addition {
+          location,
+          entry_mode,
+          ..
+        } if !entry_mode.is_tree() => (location, "added"),
+        ChangeDetached::Deletion {
+          location,
+          entry_mode,
+          ..
+        } if !entry_mode.is_tree() => (location, "deleted"),
+        ChangeDetached::Modification {
+          location,
+          entry_mode,
+          ..
+        } if !entry_mode.is_tree() => (location, "modified"),
+        _ => continue,
+      };
+      files.push(FileDiffSummaryItem {
+        path: location.to_str_lossy().to_string(),
+        status: status.to_string(),
+        staged: false,
+      });
+    }
+
+    // Third parent: a root commit holding only the untracked files.
+    if let Some(untracked_id) = parents.get(2) {
+      let mut recorder = gix::traverse::tree::Recorder::default();
+      commit_tree(repo, *untracked_id)?
+        .traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to traverse untracked tree: {e}")))?;
+      for record in recorder.records {
+        if !record.mode.is_tree() {
+          files.push(FileDiffSummaryItem {
+            path: record.filepath.to_str_lossy().to_string(),
+            status: "added".to_string(),
+            staged: false,
+          });
+        }
+      }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let total = files.len() as u32;
+    Ok(DiffSummaryResult {
+      files,
+      total,
+      truncated: false,
+    })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::{apply_stash_entry, drop_stash_entry, push_stash};
+  use crate::test_util::{git, TempDir};
+
+  /// A repo on `main` with `a.txt` and `b.txt` committed, `a.txt` changed
+  /// and staged, `b.txt` changed but not staged, and untracked `new/c.txt`.
+  fn repo_with_changes(name: &str) -> TempDir {
+    let dir = TempDir::repo(name);
+    // Stash commits are written by gix with the repository's identity.
+    git(&dir, &["config", "user.name", "Test"]);
+    git(&dir, &["config", "user.email", "test@example.com"]);
+    std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+    std::fs::write(dir.join("b.txt"), "b\n").unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "first"]);
+
+    std::fs::write(dir.join("a.txt"), "a staged\n").unwrap();
+    git(&dir, &["add", "a.txt"]);
+    std::fs::write(dir.join("b.txt"), "b unstaged\n").unwrap();
+    std::fs::create_dir_all(dir.join("new")).unwrap();
+    std::fs::write(dir.join("new/c.txt"), "c\n").unwrap();
+    dir
+  }
+
+  fn status(dir: &Path) -> String {
+    git(dir, &["status", "--porcelain", "--untracked-files=all"])
+  }
+
+  #[test]
+  fn push_stash_is_readable_by_git() {
+    let dir = repo_with_changes("stash-push");
+    let before = status(&dir);
+    let short = git(&dir, &["rev-parse", "--short", "HEAD"]);
+
+    let repo = gix::open(&*dir).unwrap();
+    let stash = push_stash(&repo, None, true).unwrap();
+    assert_eq!(git(&dir, &["rev-parse", "refs/stash"]), stash);
+    assert_eq!(status(&dir), "");
+    assert!(!dir.join("new").exists());
+    assert_eq!(
+      git(&dir, &["stash", "list"]),
+      format!("stash@{{0}}: WIP on main: {short} first")
+    );
+
+    git(&dir, &["stash", "pop", "-q", "--index"]);
+    assert_eq!(status(&dir), before);
+    assert_eq!(
+      std::fs::read_to_string(dir.join("b.txt")).unwrap(),
+      "b unstaged\n"
+    );
+  }
+
+  #[test]
+  fn push_stash_fails_on_clean_worktree() {
+    let dir = repo_with_changes("stash-clean");
+    git(&dir, &["stash", "-q", "-u"]);
+
+    let repo = gix::open(&*dir).unwrap();
+    let err = push_stash(&repo, Some("nothing"), true).unwrap_err();
+    assert!(err.reason.contains("Nothing to stash"), "{}", err.reason);
+  }
+
+  #[test]
+  fn apply_stash_entry_restores_git_stash() {
+    let dir = repo_with_changes("stash-apply");
+    let before = status(&dir);
+    git(&dir, &["stash", "-q", "-u"]);
+
+    let repo = gix::open(&*dir).unwrap();
+    apply_stash_entry(&repo, 0).unwrap();
+    assert_eq!(status(&dir), before);
+    assert_eq!(
+      std::fs::read_to_string(dir.join("new/c.txt")).unwrap(),
+      "c\n"
+    );
+    assert_eq!(git(&dir, &["stash", "list"]).lines().count(), 1);
+  }
+
+  #[test]
+  fn apply_stash_entry_refuses_to_overwrite_local_changes() {
+    let dir = repo_with_changes("stash-apply-dirty");
+    git(&dir, &["stash", "-q"]);
+    std::fs::write(dir.join("b.txt"), "b local\n").unwrap();
+
+    let repo = gix::open(&*dir).unwrap();
+    let err = apply_stash_entry(&repo, 0).unwrap_err();
+    assert!(err.reason.contains("b.txt"), "{}", err.reason);
+    assert_eq!(
+      std::fs::read_to_string(dir.join("b.txt")).unwrap(),
+      "b local\n"
+    );
+  }
+
+  #[test]
+  fn drop_stash_entry_rewrites_reflog() {
+    let dir = repo_with_changes("stash-drop");
+    for n in 0..3 {
+      std::fs::write(dir.join("b.txt"), format!("b {n}\n")).unwrap();
+      git(&dir, &["stash", "push", "-q", "-m", &format!("stash {n}")]);
+    }
+    let oldest = git(&dir, &["rev-parse", "stash@{2}"]);
+
+    let repo = gix::open(&*dir).unwrap();
+    drop_stash_entry(&repo, 1).unwrap();
+    assert_eq!(
+      git(&dir, &["stash", "list", "--format=%gs"]),
+      "On main: stash 2\nOn main: stash 0"
+    );
+    assert_eq!(git(&dir, &["rev-parse", "stash@{1}"]), oldest);
+
+    drop_stash_entry(&repo, 0).unwrap();
+    assert_eq!(git(&dir, &["rev-parse", "refs/stash"]), oldest);
+    assert_eq!(git(&dir, &["stash", "list"]).lines().count(), 1);
+    git(&dir, &["reflog", "exists", "refs/stash"]);
+
+    drop_stash_entry(&repo, 0).unwrap();
+    assert_eq!(git(&dir, &["stash", "list"]), "");
+    assert!(gix::open(&*dir)
+      .unwrap()
+      .find_reference("refs/stash")
+      .is_err());
+  }
+}
@@ -0,0 +1,68 @@
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::status_summary::GitStatusSummary;
+use crate::whitespace::IgnoreWhitespace;
+
+/// Default time-to-live for cached status summaries. Short enough that the UI
+/// never shows noticeably stale data, long enough to collapse the burst of
+/// identical queries several components fire for the same worktree at once.
+pub(crate) const DEFAULT_STATUS_TTL: Duration = Duration::from_millis(500);
+
+/// Entries older than this are dropped whenever a new one is stored, so
+/// worktrees that are no longer queried don't stay cached forever. A longer
+/// `cache_ttl_ms` is effectively capped at this.
+const MAX_STATUS_AGE: Duration = Duration::from_secs(60);
+
+/// Everything that shapes a status summary.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct StatusKey {
+  pub(crate) worktree_cwd: String,
+  pub(crate) base_branch: Option<String>,
+  pub(crate) project_cwd: Option<String>,
+  pub(crate) pathspecs: Vec<String>,
+  pub(crate) ignore_whitespace: IgnoreWhitespace,
+  /// As passed, after checking it names a supported algorithm.
+  pub(crate) diff_algorithm: Option<String>,
+  pub(crate) include_staged: bool,
+}
+
+/// Process-wide status cache. Unlike the repo handle cache this is shared
+/// across libuv worker threads, since concurrent calls for the same worktree
+/// usually land on different threads.
+static STATUS_CACHE: LazyLock<DashMap<StatusKey, (Instant, GitStatusSummary)>> =
+  LazyLock::new(DashMap::new);
+
+/// Return a cached summary for `key` if it is younger than `ttl`.
+pub(crate) fn get_cached_status(key: &StatusKey, ttl: Duration) -> Option<GitStatusSummary> {
+  let entry = STATUS_CACHE.get(key)?;
+  let (computed_at, summary) = entry.value();
+  if computed_at.elapsed() < ttl {
+    Some(summary.clone())
+  } else {
+    None
+  }
+}
+
+/// Store a freshly computed summary, pruning entries past `MAX_STATUS_AGE`.
+pub(crate) fn put_cached_status(key: StatusKey, summary: &GitStatusSummary) {
+  STATUS_CACHE.retain(|_, (computed_at, _)| computed_at.elapsed() < MAX_STATUS_AGE);
+  STATUS_CACHE.insert(key, (Instant::now(), summary.clone()));
+}
+
+/// Drop every cached summary for a worktree, whatever the other options.
+pub(crate) fn invalidate_status(worktree_cwd: &str) {
+  STATUS_CACHE.retain(|key, _| key.worktree_cwd != worktree_cwd);
+}
+
+/// Invalidate cached `get_status_summary` results for `cwd`.
+///
+/// Call after operations that change the worktree or refs (commit, stage,
+/// checkout, ...) so the next status query recomputes instead of returning
+/// a result up to one TTL old.
+#[napi]
+pub fn clear_status_cache(cwd: String) {
+  invalidate_status(&cwd);
+}
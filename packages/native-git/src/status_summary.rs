@@ -1,9 +1,16 @@
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use gix::bstr::{BString, ByteSlice};
 use gix::worktree::stack::state::attributes::Source as AttrSource;
+use rayon::prelude::*;
 
+use crate::attributes::{counting_attributes, path_attrs};
+use crate::diff_summary::staged_changes;
+use crate::file_diff::is_binary;
 use crate::repo_cache::with_repo;
+use crate::status_cache::{get_cached_status, put_cached_status, StatusKey, DEFAULT_STATUS_TTL};
+use crate::whitespace::{normalize_whitespace, IgnoreWhitespace};
 
 const MAX_UNTRACKED_TO_COUNT: usize = 200;
 const MAX_UNTRACKED_FILE_SIZE: u64 = 512 * 1024; // 512 KB
@@ -11,7 +18,17 @@ const MAX_UNTRACKED_FILE_SIZE: u64 = 512 * 1024; // 512 KB
 #[napi(object)]
 #[derive(Debug, Clone)]
 pub struct GitStatusSummary {
+  /// `tracked_modified_count + untracked_file_count`. Deprecated: will be
+  /// removed in a future version in favour of the two separate counts.
   pub dirty_file_count: u32,
+  /// Tracked files with staged or unstaged changes (including deletions and
+  /// dirty submodules).
+  pub tracked_modified_count: u32,
+  pub untracked_file_count: u32,
+  /// Files whose index entry differs from HEAD (added, modified, deleted or
+  /// unmerged), like `git diff --cached --name-only`. Only computed with
+  /// `StatusSummaryOptions.include_staged`; 0 otherwise.
+  pub staged_file_count: u32,
   pub unpushed_commit_count: u32,
   pub unpulled_commit_count: u32,
   pub has_remote_branch: bool,
@@ -54,6 +71,8 @@ fn count_lines_for_entry(
   worktree_path: &Path,
   rel_path_str: &str,
   head_tree: &gix::Tree<'_>,
+  algorithm: gix::diff::blob::Algorithm,
+  ignore_whitespace: IgnoreWhitespace,
 ) -> (u32, u32) {
   let disk_path = worktree_path.join(rel_path_str);
 
@@ -70,20 +89,36 @@ fn count_lines_for_entry(
   let old_bytes = old_data.as_deref().unwrap_or(b"");
   let new_bytes = new_data.as_deref().unwrap_or(b"");
 
-  // Quick binary check (null bytes in first 8KB)
-  let is_binary = |data: &[u8]| -> bool {
-    let check_len = data.len().min(8192);
-    data[..check_len].contains(&0)
-  };
-
   if is_binary(old_bytes) || is_binary(new_bytes) {
     return (0, 0);
   }
 
-  // Use imara-diff for accurate line counting
-  let input = gix::diff::blob::intern::InternedInput::new(old_bytes, new_bytes);
-  let counter = LineCounter::default();
-  gix::diff::blob::diff(gix::diff::blob::Algorithm::Histogram, &input, counter)
+  let old_bytes = normalize_whitespace(old_bytes, ignore_whitespace);
+  let new_bytes = normalize_whitespace(new_bytes, ignore_whitespace);
+  count_line_changes(&old_bytes, &new_bytes, algorithm)
+}
+
+/// Map a diff algorithm name to its imara-diff implementation: `"histogram"`
+/// (the default when `None`), `"myers"` or `"minimal"` (Myers without
+/// heuristics, git's `--minimal`). Git's `patience` has no imara-diff
+/// counterpart and is rejected.
+pub(crate) fn parse_diff_algorithm(name: Option<&str>) -> napi::Result<gix::diff::blob::Algorithm> {
+  match name.unwrap_or("histogram") {
+    "histogram" => Ok(gix::diff::blob::Algorithm::Histogram),
+    "myers" => Ok(gix::diff::blob::Algorithm::Myers),
+    "minimal" => Ok(gix::diff::blob::Algorithm::MyersMinimal),
+    other => Err(napi::Error::from_reason(format!("Unsupported diff algorithm: {other}"))),
+  }
+}
+
+/// Count added and deleted lines between two texts with imara-diff.
+pub(crate) fn count_line_changes(
+  old: &[u8],
+  new: &[u8],
+  algorithm: gix::diff::blob::Algorithm,
+) -> (u32, u32) {
+  let input = gix::diff::blob::intern::InternedInput::new(old, new);
+  gix::diff::blob::diff(algorithm, &input, LineCounter::default())
 }
 
 /// Sink for imara-diff that counts added and deleted lines.
@@ -106,42 +141,6 @@ impl gix::diff::blob::Sink for LineCounter {
   }
 }
 
-/// Check `.gitattributes` to determine if a file should be treated as binary.
-/// Returns true if `binary` is Set or `diff` is Unset (i.e. `-diff`).
-fn is_binary_by_attr(
-  attr_stack: &mut gix::worktree::Stack,
-  outcome: &mut gix::attrs::search::Outcome,
-  rel_path: &str,
-  objects: &dyn gix::objs::Find,
-) -> bool {
-  let platform = match attr_stack.at_entry(rel_path.as_bytes().as_bstr(), None, objects) {
-    Ok(p) => p,
-    Err(_) => return false,
-  };
-
-  if !platform.matching_attributes(outcome) {
-    return false;
-  }
-
-  for m in outcome.iter() {
-    match m.assignment.name.as_str() {
-      "binary" => {
-        if matches!(m.assignment.state, gix::attrs::StateRef::Set) {
-          return true;
-        }
-      }
-      "diff" => {
-        if matches!(m.assignment.state, gix::attrs::StateRef::Unset) {
-          return true;
-        }
-      }
-      _ => {}
-    }
-  }
-
-  false
-}
-
 /// True if the nested git repo at `nested_path` reports any uncommitted changes.
 /// Used to surface dirtiness for stranded gitlinks (entries with mode 160000
 /// that aren't registered in `.gitmodules`), which gix's submodule status
@@ -156,6 +155,63 @@ fn is_nested_repo_dirty_status(nested_path: &Path) -> bool {
     .unwrap_or(false)
 }
 
+/// Remote-tracking ref of `branch`'s upstream: the configured one
+/// (`branch.<name>.remote`/`merge`), else the conventional `origin/<branch>`.
+/// The ref may not exist.
+pub(crate) fn upstream_ref_name(repo: &gix::Repository, branch: &str) -> String {
+  let configured_upstream = gix::refs::FullName::try_from(format!("refs/heads/{}", branch))
+    .ok()
+    .and_then(|name| {
+      repo
+        .branch_remote_tracking_ref_name(name.as_ref(), gix::remote::Direction::Fetch)
+        .and_then(Result::ok)
+        .map(|upstream| upstream.as_bstr().to_string())
+    });
+  configured_upstream.unwrap_or_else(|| format!("refs/remotes/origin/{}", branch))
+}
+
+/// `info/exclude` of the repository shared by a linked worktree, or `None`
+/// for a main worktree. Git applies it to every worktree, but gix only
+/// looks for `info/exclude` in the worktree's private git dir
+/// (`.git/worktrees/<name>`), so untracked files it ignores would otherwise
+/// be counted as dirty there. `.gitignore` files need no such help: each
+/// worktree has its own checkout of them.
+pub(crate) fn shared_excludes(repo: &gix::Repository) -> Option<gix::ignore::Search> {
+  if repo.git_dir() == repo.common_dir() {
+    return None;
+  }
+  let exclude_file = repo.common_dir().join("info").join("exclude");
+  let bytes = std::fs::read(&exclude_file).ok()?;
+  let mut search = gix::ignore::Search::default();
+  search.add_patterns_buffer(
+    &bytes,
+    exclude_file,
+    None,
+    gix::ignore::search::Ignore::default(),
+  );
+  Some(search)
+}
+
+/// True if `rela_path` or one of its parent directories matches an
+/// exclude pattern (the last matching pattern wins, as in git).
+pub(crate) fn is_excluded(search: &gix::ignore::Search, rela_path: &str) -> bool {
+  let case = gix::glob::pattern::Case::Sensitive;
+  let mut dir_end = 0;
+  while let Some(pos) = rela_path[dir_end..].find('/') {
+    dir_end += pos;
+    let dir = rela_path.as_bytes()[..dir_end].as_bstr();
+    if let Some(m) = search.pattern_matching_relative_path(dir, Some(true), case) {
+      if !m.pattern.is_negative() {
+        return true;
+      }
+    }
+    dir_end += 1;
+  }
+  search
+    .pattern_matching_relative_path(rela_path.as_bytes().as_bstr(), Some(false), case)
+    .is_some_and(|m| !m.pattern.is_negative())
+}
+
 /// Count commits between two refs using `git rev-list --count <range>`.
 /// Falls back to 0 on any error.
 fn rev_list_count(cwd: &Path, range: &str) -> u32 {
@@ -176,7 +232,9 @@ fn rev_list_count(cwd: &Path, range: &str) -> u32 {
 
 /// Intermediate result from the main status scan (Phase 1 + 2a).
 struct StatusPhaseResult {
-  dirty_file_count: u32,
+  tracked_modified_count: u32,
+  untracked_file_count: u32,
+  staged_file_count: u32,
   unpushed_commit_count: u32,
   unpulled_commit_count: u32,
   has_remote_branch: bool,
@@ -185,15 +243,205 @@ struct StatusPhaseResult {
   branch_name: Option<String>,
 }
 
+/// Files whose index entry differs from HEAD (everything in the index when
+/// there is no HEAD yet), counting each path once.
+fn count_staged_files<'repo>(
+  repo: &'repo gix::Repository,
+  index: &gix::index::File,
+  pathspec: &mut Option<gix::Pathspec<'repo>>,
+) -> napi::Result<u32> {
+  Ok(staged_changes(repo, index, pathspec.as_mut())?.len() as u32)
+}
+
+/// Summarize a worktree's status relative to its upstream and `base_branch`.
+/// The upstream is the branch's configured one (see `set_upstream`), or
+/// `origin/<branch>` when none is configured.
+///
+/// Results are cached per worktree and set of options for
+/// `options.cache_ttl_ms`, so concurrent queries for the same worktree don't
+/// each rescan it. Use `clear_status_cache` to invalidate.
 #[napi]
 pub async fn get_status_summary(
   worktree_cwd: String,
   base_branch: Option<String>,
   project_cwd: Option<String>,
+  options: Option<StatusSummaryOptions>,
 ) -> napi::Result<GitStatusSummary> {
+  let options = options.unwrap_or_default();
+  let key = status_key(worktree_cwd, base_branch, project_cwd, &options)?;
+  cached_status_summary(key, status_ttl(options.cache_ttl_ms))
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct StatusSummaryOptions {
+  /// How long a cached summary stays valid, in milliseconds (default 500,
+  /// at most 60 s; 0 bypasses the cache).
+  pub cache_ttl_ms: Option<u32>,
+  /// Git pathspecs (e.g. `packages/client`) limiting the dirty file count
+  /// and line counts to matching paths; all files when absent.
+  pub pathspecs: Option<Vec<String>>,
+  /// `"none"`, `"leading"`, `"trailing"` or `"all"`: keeps whitespace-only
+  /// edits out of the line counts of modified files.
+  pub ignore_whitespace: Option<String>,
+  /// Line-counting diff algorithm: `"histogram"` (default), `"myers"` or
+  /// `"minimal"`.
+  pub diff_algorithm: Option<String>,
+  /// Also compute `staged_file_count`, an extra pass over the index and the
+  /// HEAD tree (default false).
+  pub include_staged: Option<bool>,
+}
+
+/// Arguments for one worktree in a `batch_get_status_summary` call; mirrors
+/// the parameters of `get_status_summary`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StatusSummaryRequest {
+  pub worktree_cwd: String,
+  pub base_branch: Option<String>,
+  pub project_cwd: Option<String>,
+  pub options: Option<StatusSummaryOptions>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BatchStatusResult {
+  pub worktree_cwd: String,
+  pub result: Option<GitStatusSummary>,
+  pub error: Option<String>,
+}
+
+/// Summarize many worktrees in one call, scanning them in parallel. Results
+/// are returned in request order; a failure for one worktree is reported in
+/// its `error` field instead of failing the whole batch.
+#[napi]
+pub async fn batch_get_status_summary(
+  requests: Vec<StatusSummaryRequest>,
+) -> napi::Result<Vec<BatchStatusResult>> {
+  let results = requests
+    .into_par_iter()
+    .map(|req| {
+      let options = req.options.unwrap_or_default();
+      let ttl = status_ttl(options.cache_ttl_ms);
+      let outcome = status_key(
+        req.worktree_cwd.clone(),
+        req.base_branch,
+        req.project_cwd,
+        &options,
+      )
+      .and_then(|key| cached_status_summary(key, ttl));
+      match outcome {
+        Ok(summary) => BatchStatusResult {
+          worktree_cwd: req.worktree_cwd,
+          result: Some(summary),
+          error: None,
+        },
+        Err(e) => BatchStatusResult {
+          worktree_cwd: req.worktree_cwd,
+          result: None,
+          error: Some(e.reason.clone()),
+        },
+      }
+    })
+    .collect();
+  Ok(results)
+}
+
+/// Whether the worktree has any change `get_status_summary` would count:
+/// staged changes, modified or deleted tracked files, or untracked files.
+/// Stops at the first change found and skips line counting and branch
+/// analysis, so it's much cheaper than the full summary.
+#[napi]
+pub async fn is_dirty(cwd: String) -> napi::Result<bool> {
+  with_repo(&cwd, has_changes)
+}
+
+/// `is_dirty` for an already opened repository.
+pub(crate) fn has_changes(repo: &gix::Repository) -> napi::Result<bool> {
+  let status_iter = repo
+    .status(gix::progress::Discard)
+    .map_err(|e| napi::Error::from_reason(format!("Failed to create status: {e}")))?
+    .untracked_files(gix::status::UntrackedFiles::Files)
+    // Same submodule handling as the summary's status scan.
+    .index_worktree_submodules(gix::status::Submodule::Given {
+      ignore: gix::submodule::config::Ignore::None,
+      check_dirty: true,
+    })
+    .into_iter(Vec::<BString>::new())
+    .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
+
+  // Covers both HEAD-vs-index and index-vs-worktree changes; returning
+  // drops the iterator, which stops the scan. Untracked files excluded by
+  // the shared `info/exclude` are skipped, as in the summary.
+  let excludes = shared_excludes(repo);
+  for item in status_iter {
+    let item =
+      item.map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
+    if let (
+      Some(search),
+      gix::status::Item::IndexWorktree(gix::status::index_worktree::Item::DirectoryContents {
+        entry,
+        ..
+      }),
+    ) = (&excludes, &item)
+    {
+      if is_excluded(search, &entry.rela_path.to_str_lossy()) {
+        continue;
+      }
+    }
+    return Ok(true);
+  }
+  Ok(false)
+}
+
+fn status_key(
+  worktree_cwd: String,
+  base_branch: Option<String>,
+  project_cwd: Option<String>,
+  options: &StatusSummaryOptions,
+) -> napi::Result<StatusKey> {
+  // Checked here so a bad name fails before the cache lookup.
+  parse_diff_algorithm(options.diff_algorithm.as_deref())?;
+  Ok(StatusKey {
+    worktree_cwd,
+    base_branch,
+    project_cwd,
+    pathspecs: options.pathspecs.clone().unwrap_or_default(),
+    ignore_whitespace: IgnoreWhitespace::parse(options.ignore_whitespace.as_deref())?,
+    diff_algorithm: options.diff_algorithm.clone(),
+    include_staged: options.include_staged.unwrap_or(false),
+  })
+}
+
+fn status_ttl(cache_ttl_ms: Option<u32>) -> Duration {
+  cache_ttl_ms
+    .map(|ms| Duration::from_millis(ms as u64))
+    .unwrap_or(DEFAULT_STATUS_TTL)
+}
+
+/// Return the cached summary if it is younger than `ttl` (a zero TTL always
+/// recomputes), otherwise compute and cache a fresh one.
+fn cached_status_summary(key: StatusKey, ttl: Duration) -> napi::Result<GitStatusSummary> {
+  if !ttl.is_zero() {
+    if let Some(cached) = get_cached_status(&key, ttl) {
+      return Ok(cached);
+    }
+  }
+
+  let summary = compute_status_summary(&key)?;
+  put_cached_status(key, &summary);
+  Ok(summary)
+}
+
+fn compute_status_summary(key: &StatusKey) -> napi::Result<GitStatusSummary> {
+  let worktree_cwd = key.worktree_cwd.as_str();
+  let base_branch = key.base_branch.as_deref();
+  let pathspecs = key.pathspecs.as_slice();
+  let algorithm = parse_diff_algorithm(key.diff_algorithm.as_deref())?;
+
   // Phase 1 + 2a: status scan, line counting, branch analysis — all from worktree repo
-  let phase1 = with_repo(&worktree_cwd, |repo| {
-    let worktree_path = PathBuf::from(&worktree_cwd);
+  let phase1 = with_repo(worktree_cwd, |repo| {
+    let worktree_path = PathBuf::from(worktree_cwd);
 
     // Get HEAD reference
     let head_ref = repo.head_ref()
@@ -206,20 +454,11 @@ pub async fn get_status_summary(
     // Get HEAD commit and tree for diffing (optional — may not exist for new repos)
     let head_tree = repo.head_commit().ok().and_then(|c| c.tree().ok());
 
-    // ── Set up attribute stack for .gitattributes binary detection ──
-    let (mut attr_stack, mut attr_outcome) = match repo.open_index() {
-      Ok(index) => {
-        match repo.attributes_only(&index, AttrSource::WorktreeThenIdMapping) {
-          Ok(attr_handle) => {
-            let outcome = attr_handle.selected_attribute_matches(["binary", "diff"]);
-            let stack = attr_handle.detach();
-            (Some(stack), Some(outcome))
-          }
-          Err(_) => (None, None),
-        }
-      }
-      Err(_) => (None, None),
-    };
+    // ── Set up attribute stack for .gitattributes binary / LFS detection ──
+    let mut counting_attrs = repo
+      .open_index()
+      .ok()
+      .and_then(|index| counting_attributes(repo, &index));
 
     // ── Phase 1: Status (dirty files, untracked, line counting) ──
 
@@ -233,12 +472,15 @@ pub async fn get_status_summary(
         check_dirty: true,
       });
 
-    let empty_patterns: Vec<BString> = Vec::new();
+    // Pathspec patterns scope the scan (empty = all files)
+    let patterns: Vec<BString> = pathspecs.iter().map(|p| BString::from(p.as_str())).collect();
     let status_iter = status_platform
-      .into_index_worktree_iter(empty_patterns)
+      .into_index_worktree_iter(patterns.clone())
       .map_err(|e| napi::Error::from_reason(format!("Failed to iterate status: {e}")))?;
 
-    let mut dirty_file_count: u32 = 0;
+    let excludes = shared_excludes(repo);
+    let mut tracked_modified_count: u32 = 0;
+    let mut untracked_file_count: u32 = 0;
     let mut untracked_paths: Vec<PathBuf> = Vec::new();
     let mut untracked_rel_paths: Vec<String> = Vec::new();
     let mut modified_rel_paths: Vec<String> = Vec::new();
@@ -250,21 +492,31 @@ pub async fn get_status_summary(
       let entry = entry
         .map_err(|e| napi::Error::from_reason(format!("Status iteration error: {e}")))?;
 
-      dirty_file_count += 1;
+      if let gix::status::index_worktree::Item::DirectoryContents { entry: dir, .. } = &entry {
+        let rela_path = dir.rela_path.to_str_lossy();
+        if let Some(search) = &excludes {
+          if is_excluded(search, &rela_path) {
+            continue;
+          }
+        }
+      }
 
       match &entry {
         gix::status::index_worktree::Item::Modification { rela_path, .. } => {
+          tracked_modified_count += 1;
           let rel_str = rela_path.to_string();
           worktree_changed_paths.insert(rel_str.clone());
           modified_rel_paths.push(rel_str);
         }
         gix::status::index_worktree::Item::DirectoryContents { entry: dir_entry, .. } => {
+          untracked_file_count += 1;
           let rel_str = dir_entry.rela_path.to_string();
           worktree_changed_paths.insert(rel_str.clone());
           untracked_paths.push(worktree_path.join(&rel_str));
           untracked_rel_paths.push(rel_str);
         }
         gix::status::index_worktree::Item::Rewrite { dirwalk_entry, .. } => {
+          tracked_modified_count += 1;
           worktree_changed_paths.insert(dirwalk_entry.rela_path.to_string());
         }
       }
@@ -275,11 +527,27 @@ pub async fn get_status_summary(
       .open_index()
       .map_err(|e| napi::Error::from_reason(format!("Failed to open index: {e}")))?;
 
+    // The index pass below must honor the same pathspecs as the status scan.
+    let mut index_pathspec = if patterns.is_empty() {
+      None
+    } else {
+      Some(
+        repo
+          .pathspec(true, &patterns, true, &index, AttrSource::IdMapping)
+          .map_err(|e| napi::Error::from_reason(format!("Invalid pathspec: {e}")))?,
+      )
+    };
+
     let mut staged_new_paths: Vec<PathBuf> = Vec::new();
     let mut staged_new_rel_paths: Vec<String> = Vec::new();
     let mut staged_modified_rel_paths: Vec<String> = Vec::new();
 
     for idx_entry in index.entries().iter() {
+      if let Some(ref mut pathspec) = index_pathspec {
+        if !pathspec.is_included(idx_entry.path(&index), Some(false)) {
+          continue;
+        }
+      }
       let path_str = idx_entry.path(&index).to_str_lossy().to_string();
       if worktree_changed_paths.contains(&path_str) {
         continue;
@@ -290,20 +558,20 @@ pub async fn get_status_summary(
           Ok(Some(tree_entry)) => {
             if tree_entry.object_id() != idx_entry.id {
               // Staged modification: file exists in HEAD but index differs — needs a real diff
-              dirty_file_count += 1;
+              tracked_modified_count += 1;
               staged_modified_rel_paths.push(path_str);
             }
           }
           _ => {
             // Staged new file: not in HEAD tree — count all lines as additions
-            dirty_file_count += 1;
+            tracked_modified_count += 1;
             staged_new_paths.push(worktree_path.join(&path_str));
             staged_new_rel_paths.push(path_str);
           }
         },
         None => {
           // No HEAD commit — everything is new
-          dirty_file_count += 1;
+          tracked_modified_count += 1;
           staged_new_paths.push(worktree_path.join(&path_str));
           staged_new_rel_paths.push(path_str);
         }
@@ -315,12 +583,19 @@ pub async fn get_status_summary(
     // (files where the index differs from HEAD but the worktree matches the index).
     if let Some(ref tree) = head_tree {
       for rel_path_str in modified_rel_paths.iter().chain(staged_modified_rel_paths.iter()) {
-        if let (Some(ref mut stack), Some(ref mut outcome)) = (&mut attr_stack, &mut attr_outcome) {
-          if is_binary_by_attr(stack, outcome, rel_path_str, &repo.objects) {
+        if let Some((ref mut stack, ref mut outcome)) = counting_attrs {
+          if path_attrs(stack, outcome, rel_path_str, &repo.objects).skip_line_count() {
             continue;
           }
         }
-        let (added, deleted) = count_lines_for_entry(repo, &worktree_path, rel_path_str, tree);
+        let (added, deleted) = count_lines_for_entry(
+          repo,
+          &worktree_path,
+          rel_path_str,
+          tree,
+          algorithm,
+          key.ignore_whitespace,
+        );
         lines_added += added;
         lines_deleted += deleted;
       }
@@ -333,8 +608,8 @@ pub async fn get_status_summary(
       if i >= MAX_UNTRACKED_TO_COUNT {
         break;
       }
-      if let (Some(ref mut stack), Some(ref mut outcome)) = (&mut attr_stack, &mut attr_outcome) {
-        if is_binary_by_attr(stack, outcome, rel_path, &repo.objects) {
+      if let Some((ref mut stack, ref mut outcome)) = counting_attrs {
+        if path_attrs(stack, outcome, rel_path, &repo.objects).skip_line_count() {
           continue;
         }
       }
@@ -348,6 +623,13 @@ pub async fn get_status_summary(
       lines_added += count_file_lines(path);
     }
 
+    // ── Phase 1b: index vs HEAD, only when asked for ──
+    let staged_file_count = if key.include_staged {
+      count_staged_files(repo, &index, &mut index_pathspec)?
+    } else {
+      0
+    };
+
     // ── Phase 1c: stranded gitlinks (gitlinks without `.gitmodules`) ──
     // Mirrors the Phase 3 logic in diff_summary.rs: gix only inspects
     // submodules listed in `.gitmodules`, so detect dirty bare gitlinks
@@ -368,7 +650,7 @@ pub async fn get_status_summary(
         continue;
       }
       worktree_changed_paths.insert(path_str);
-      dirty_file_count += 1;
+      tracked_modified_count += 1;
     }
 
     // ── Phase 2a: Branch analysis ──
@@ -377,7 +659,9 @@ pub async fn get_status_summary(
       Some(b) => b.clone(),
       None => {
         return Ok(StatusPhaseResult {
-          dirty_file_count,
+          tracked_modified_count,
+          untracked_file_count,
+          staged_file_count,
           unpushed_commit_count: 0,
           unpulled_commit_count: 0,
           has_remote_branch: false,
@@ -389,8 +673,8 @@ pub async fn get_status_summary(
     };
 
     // Check for upstream/remote branch
-    let upstream_ref_name = format!("refs/remotes/origin/{}", branch);
-    let has_remote_branch = repo.find_reference(&upstream_ref_name).is_ok();
+    let upstream_ref_name = upstream_ref_name(repo, &branch);
+    let has_remote_branch = repo.find_reference(upstream_ref_name.as_str()).is_ok();
 
     // Count unpushed and unpulled commits using git rev-list --count.
     // The gix rev_walk approach breaks with merge commits because
@@ -400,14 +684,18 @@ pub async fn get_status_summary(
     let mut unpulled_commit_count: u32 = 0;
 
     if has_remote_branch {
-      unpushed_commit_count = rev_list_count(&worktree_path, &format!("origin/{}..HEAD", branch));
-      unpulled_commit_count = rev_list_count(&worktree_path, &format!("HEAD..origin/{}", branch));
-    } else if let Some(ref base_b) = base_branch {
+      unpushed_commit_count =
+        rev_list_count(&worktree_path, &format!("{}..HEAD", upstream_ref_name));
+      unpulled_commit_count =
+        rev_list_count(&worktree_path, &format!("HEAD..{}", upstream_ref_name));
+    } else if let Some(base_b) = base_branch {
       unpushed_commit_count = rev_list_count(&worktree_path, &format!("{}..HEAD", base_b));
     }
 
     Ok(StatusPhaseResult {
-      dirty_file_count,
+      tracked_modified_count,
+      untracked_file_count,
+      staged_file_count,
       unpushed_commit_count,
       unpulled_commit_count,
       has_remote_branch,
@@ -419,8 +707,8 @@ pub async fn get_status_summary(
 
   // Phase 2b: merge-base check — may use a different repo path (project root vs worktree)
   // This is a SEPARATE with_repo() call to avoid RefCell re-entrancy panic.
-  let is_merged_into_base = if let (Some(ref base_b), Some(ref branch)) = (&base_branch, &phase1.branch_name) {
-    let project_path = project_cwd.as_deref().unwrap_or(&worktree_cwd);
+  let is_merged_into_base = if let (Some(base_b), Some(ref branch)) = (base_branch, &phase1.branch_name) {
+    let project_path = key.project_cwd.as_deref().unwrap_or(worktree_cwd);
     with_repo(project_path, |project_repo| {
       let base_ref_name = format!("refs/heads/{}", base_b);
       if let Ok(base_ref) = project_repo.find_reference(&base_ref_name) {
@@ -442,7 +730,10 @@ pub async fn get_status_summary(
   };
 
   Ok(GitStatusSummary {
-    dirty_file_count: phase1.dirty_file_count,
+    dirty_file_count: phase1.tracked_modified_count + phase1.untracked_file_count,
+    tracked_modified_count: phase1.tracked_modified_count,
+    untracked_file_count: phase1.untracked_file_count,
+    staged_file_count: phase1.staged_file_count,
     unpushed_commit_count: phase1.unpushed_commit_count,
     unpulled_commit_count: phase1.unpulled_commit_count,
     has_remote_branch: phase1.has_remote_branch,
@@ -451,3 +742,116 @@ pub async fn get_status_summary(
     lines_deleted: phase1.lines_deleted,
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{compute_status_summary, count_line_changes, has_changes, parse_diff_algorithm};
+  use crate::status_cache::StatusKey;
+  use crate::test_util::{git, TempDir};
+  use crate::whitespace::IgnoreWhitespace;
+
+  const ALGORITHMS: [&str; 3] = ["histogram", "myers", "minimal"];
+
+  #[test]
+  fn all_algorithms_agree_on_simple_edit() {
+    let old = b"fn main() {\n    println!(\"hello\");\n}\n";
+    let new = b"fn main() {\n    println!(\"hello, world\");\n    return;\n}\n";
+    for name in ALGORITHMS {
+      let algorithm = parse_diff_algorithm(Some(name)).unwrap();
+      assert_eq!(count_line_changes(old, new, algorithm), (2, 1), "{name}");
+    }
+  }
+
+  #[test]
+  fn net_line_change_is_independent_of_algorithm() {
+    // Repeated lines let the algorithms pick different alignments, but the
+    // difference between added and deleted lines is always new - old.
+    let old = b"a\nb\nc\na\nb\nc\nd\n";
+    let new = b"b\na\nc\nd\na\nb\nx\nc\n";
+    for name in ALGORITHMS {
+      let algorithm = parse_diff_algorithm(Some(name)).unwrap();
+      let (added, deleted) = count_line_changes(old, new, algorithm);
+      assert_eq!(added as i64 - deleted as i64, 8 - 7, "{name}");
+    }
+  }
+
+  #[test]
+  fn default_algorithm_is_histogram() {
+    assert!(matches!(
+      parse_diff_algorithm(None),
+      Ok(gix::diff::blob::Algorithm::Histogram)
+    ));
+  }
+
+  #[test]
+  fn unknown_algorithm_is_rejected() {
+    assert!(parse_diff_algorithm(Some("patience")).is_err());
+    assert!(parse_diff_algorithm(Some("Histogram")).is_err());
+  }
+
+  #[test]
+  fn linked_worktree_honours_shared_ignore_rules() {
+    let root = TempDir::new("linked-ignores");
+    let main = root.join("main");
+    let linked = root.join("linked");
+    std::fs::create_dir_all(&main).unwrap();
+
+    git(&main, &["init", "-q"]);
+    std::fs::write(main.join(".gitignore"), "*.log\n").unwrap();
+    git(&main, &["add", ".gitignore"]);
+    git(&main, &["commit", "-q", "-m", "ignore logs"]);
+    std::fs::write(main.join(".git/info/exclude"), "*.tmp\nscratch/\n").unwrap();
+    let linked_path = linked.to_str().unwrap();
+    git(
+      &main,
+      &["worktree", "add", "-q", "-b", "linked", linked_path],
+    );
+
+    std::fs::write(linked.join("debug.log"), "ignored by .gitignore\n").unwrap();
+    std::fs::write(linked.join("notes.tmp"), "ignored by info/exclude\n").unwrap();
+    std::fs::create_dir_all(linked.join("scratch")).unwrap();
+    std::fs::write(linked.join("scratch/draft.txt"), "ignored directory\n").unwrap();
+    std::fs::write(linked.join("new.txt"), "untracked\n").unwrap();
+
+    // Git itself only reports `new.txt`.
+    assert_eq!(git(&linked, &["status", "--porcelain"]).lines().count(), 1);
+
+    let key = StatusKey {
+      worktree_cwd: linked_path.to_string(),
+      base_branch: None,
+      project_cwd: None,
+      pathspecs: Vec::new(),
+      ignore_whitespace: IgnoreWhitespace::None,
+      diff_algorithm: None,
+      include_staged: false,
+    };
+    let summary = compute_status_summary(&key).unwrap();
+    assert_eq!(summary.dirty_file_count, 1);
+    assert_eq!(summary.untracked_file_count, 1);
+  }
+
+  #[test]
+  fn has_changes_skips_shared_excludes() {
+    let root = TempDir::new("has-changes-excludes");
+    let main = root.join("main");
+    let linked = root.join("linked");
+    std::fs::create_dir_all(&main).unwrap();
+    git(&main, &["init", "-q"]);
+    git(&main, &["commit", "-q", "--allow-empty", "-m", "initial"]);
+    std::fs::write(main.join(".git/info/exclude"), "*.tmp\nscratch/\n").unwrap();
+    let linked_path = linked.to_str().unwrap();
+    git(
+      &main,
+      &["worktree", "add", "-q", "-b", "linked", linked_path],
+    );
+    std::fs::write(linked.join("notes.tmp"), "excluded\n").unwrap();
+    std::fs::create_dir_all(linked.join("scratch")).unwrap();
+    std::fs::write(linked.join("scratch/draft.txt"), "excluded directory\n").unwrap();
+
+    let repo = gix::open(&linked).unwrap();
+    assert!(!has_changes(&repo).unwrap());
+
+    std::fs::write(linked.join("notes.txt"), "untracked\n").unwrap();
+    assert!(has_changes(&repo).unwrap());
+  }
+}
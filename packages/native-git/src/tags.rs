@@ -0,0 +1,158 @@
+use gix::refs::transaction::PreviousValue;
+
+use crate::repo_cache::{evict_repo, with_repo};
+use crate::status_cache::invalidate_status;
+
+/// Full ref name for tag `name`, checked against git's ref naming rules.
+fn tag_ref_name(name: &str) -> napi::Result<gix::refs::FullName> {
+  gix::refs::FullName::try_from(format!("refs/tags/{name}"))
+    .map_err(|e| napi::Error::from_reason(format!("Invalid tag name '{name}': {e}")))
+}
+
+/// Resolve `tag_name` (short name or full `refs/tags/...`) to the commit it
+/// ultimately points at. Annotated tags, including tags of tags, are peeled
+/// all the way down; tags that end at a tree or blob are an error.
+#[napi]
+pub async fn get_tag_commit(cwd: String, tag_name: String) -> napi::Result<String> {
+  with_repo(&cwd, |repo| {
+    let ref_name = if tag_name.starts_with("refs/tags/") {
+      tag_name.clone()
+    } else {
+      format!("refs/tags/{tag_name}")
+    };
+    let reference = repo
+      .find_reference(ref_name.as_str())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to find tag {tag_name}: {e}")))?;
+    let id = reference
+      .into_fully_peeled_id()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to peel tag {tag_name}: {e}")))?;
+
+    let kind = id
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?
+      .kind;
+    if kind != gix::object::Kind::Commit {
+      return Err(napi::Error::from_reason(format!(
+        "Tag {tag_name} points to a {kind}, not a commit"
+      )));
+    }
+
+    Ok(id.to_string())
+  })
+}
+
+/// Create tag `name` pointing at `target_ref` (default HEAD) and return the
+/// hash of the object it was resolved to. Without `message` the tag is
+/// lightweight; with one it is annotated, using the configured committer
+/// identity as tagger. Fails if the tag already exists.
+#[napi]
+pub async fn create_tag(
+  cwd: String,
+  name: String,
+  target_ref: Option<String>,
+  message: Option<String>,
+) -> napi::Result<String> {
+  let result = with_repo(&cwd, |repo| {
+    let ref_name = tag_ref_name(&name)?;
+    if repo
+      .try_find_reference(ref_name.as_bstr())
+      .ok()
+      .flatten()
+      .is_some()
+    {
+      return Err(napi::Error::from_reason(format!(
+        "Tag {name} already exists"
+      )));
+    }
+
+    let target_rev = target_ref.as_deref().unwrap_or("HEAD");
+    let target = repo
+      .rev_parse_single(target_rev)
+      .map_err(|e| {
+        napi::Error::from_reason(format!("Failed to parse revision '{target_rev}': {e}"))
+      })?
+      .object()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to read object: {e}")))?;
+
+    match message {
+      Some(message) => {
+        let tagger = repo
+          .committer()
+          .ok_or_else(|| {
+            napi::Error::from_reason(
+              "No committer identity configured (user.name / user.email)".to_string(),
+            )
+          })?
+          .map_err(|e| napi::Error::from_reason(format!("Invalid committer identity: {e}")))?;
+        repo
+          .tag(
+            &name,
+            target.id,
+            target.kind,
+            Some(tagger),
+            message,
+            PreviousValue::MustNotExist,
+          )
+          .map_err(|e| napi::Error::from_reason(format!("Failed to create tag: {e}")))?;
+      }
+      None => {
+        repo
+          .tag_reference(&name, target.id, PreviousValue::MustNotExist)
+          .map_err(|e| napi::Error::from_reason(format!("Failed to create tag: {e}")))?;
+      }
+    }
+
+    Ok(target.id.to_string())
+  });
+
+  // Evict after write regardless of success/failure to ensure fresh state
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Delete `refs/tags/<name>`.
+#[napi]
+pub async fn delete_tag(cwd: String, name: String) -> napi::Result<()> {
+  let result = with_repo(&cwd, |repo| {
+    let ref_name = tag_ref_name(&name)?;
+    repo
+      .find_reference(ref_name.as_bstr())
+      .map_err(|e| napi::Error::from_reason(format!("Failed to find tag {name}: {e}")))?
+      .delete()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to delete tag {name}: {e}")))
+  });
+
+  evict_repo(&cwd);
+  invalidate_status(&cwd);
+
+  result
+}
+
+/// Name HEAD after the closest tag reachable from it, like `git describe`:
+/// `<tag>-<commits since tag>-g<abbreviated hash>`, or just `<tag>` when HEAD
+/// is tagged. Only annotated tags are candidates unless `include_lightweight`
+/// is set (`git describe --tags`). Without a reachable tag, the abbreviated
+/// HEAD hash is returned.
+#[napi]
+pub async fn describe(cwd: String, include_lightweight: Option<bool>) -> napi::Result<String> {
+  with_repo(&cwd, |repo| {
+    let names = if include_lightweight.unwrap_or(false) {
+      gix::commit::describe::SelectRef::AllTags
+    } else {
+      gix::commit::describe::SelectRef::AnnotatedTags
+    };
+    let head_commit = repo
+      .head_commit()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to get HEAD commit: {e}")))?;
+    let format = head_commit
+      .describe()
+      .names(names)
+      .id_as_fallback(true)
+      .try_format()
+      .map_err(|e| napi::Error::from_reason(format!("Failed to describe HEAD: {e}")))?
+      .ok_or_else(|| napi::Error::from_reason("Failed to describe HEAD".to_string()))?;
+    Ok(format.to_string())
+  })
+}
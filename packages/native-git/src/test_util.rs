@@ -0,0 +1,73 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Empty directory under the system temp dir, removed again on drop so a
+/// failing test doesn't leave it behind. Derefs to its path.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+  /// `name` must be unique across tests, which run in parallel.
+  pub(crate) fn new(name: &str) -> Self {
+    let dir = std::env::temp_dir().join(format!("native-git-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    TempDir(dir)
+  }
+
+  /// A fresh repository on an unborn `main` branch.
+  pub(crate) fn repo(name: &str) -> Self {
+    let dir = Self::new(name);
+    git(&dir, &["init", "-q", "-b", "main"]);
+    dir
+  }
+
+  pub(crate) fn cwd(&self) -> &str {
+    self.0.to_str().unwrap()
+  }
+}
+
+impl Deref for TempDir {
+  type Target = Path;
+
+  fn deref(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_dir_all(&self.0);
+  }
+}
+
+fn run(mut cmd: Command, dir: &Path, args: &[&str]) -> String {
+  let output = cmd
+    .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+    .args(args)
+    .current_dir(dir)
+    .output()
+    .expect("failed to run git");
+  assert!(
+    output.status.success(),
+    "git {args:?} failed: {}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+  String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Run `git <args>` in `dir` as a fixed test identity and return its
+/// trimmed stdout. Panics if git fails.
+pub(crate) fn git(dir: &Path, args: &[&str]) -> String {
+  run(Command::new("git"), dir, args)
+}
+
+/// [`git`] with the author and committer date set to `secs` since the epoch.
+pub(crate) fn git_at(dir: &Path, args: &[&str], secs: u64) -> String {
+  let date = format!("@{secs} +0000");
+  let mut cmd = Command::new("git");
+  cmd
+    .env("GIT_AUTHOR_DATE", &date)
+    .env("GIT_COMMITTER_DATE", &date);
+  run(cmd, dir, args)
+}
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+/// Which whitespace differences to disregard when comparing lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum IgnoreWhitespace {
+  /// Compare lines byte for byte.
+  #[default]
+  None,
+  /// Ignore indentation changes.
+  Leading,
+  /// Ignore whitespace at line end, including a `\r` before the newline.
+  Trailing,
+  /// Ignore changes in the amount of whitespace: runs of whitespace compare
+  /// equal to a single space and whitespace at line end is ignored.
+  SpaceChange,
+  /// Ignore all whitespace: lines differing only in whitespace are equal.
+  All,
+}
+
+/// Whitespace handling for the file diff functions, after `git diff`'s
+/// `-w` / `--ignore-space-change` / `--ignore-space-at-eol`.
+#[napi(string_enum)]
+#[derive(Debug)]
+pub enum WhitespaceMode {
+  #[napi(value = "none")]
+  None,
+  #[napi(value = "allSpace")]
+  AllSpace,
+  #[napi(value = "spaceChange")]
+  SpaceChange,
+  #[napi(value = "trailingSpace")]
+  TrailingSpace,
+}
+
+impl From<&WhitespaceMode> for IgnoreWhitespace {
+  fn from(mode: &WhitespaceMode) -> Self {
+    match mode {
+      WhitespaceMode::None => Self::None,
+      WhitespaceMode::AllSpace => Self::All,
+      WhitespaceMode::SpaceChange => Self::SpaceChange,
+      WhitespaceMode::TrailingSpace => Self::Trailing,
+    }
+  }
+}
+
+impl IgnoreWhitespace {
+  /// Parse `"none"` (the default when `None`), `"leading"`, `"trailing"` or `"all"`.
+  pub(crate) fn parse(name: Option<&str>) -> napi::Result<Self> {
+    match name.unwrap_or("none") {
+      "none" => Ok(Self::None),
+      "leading" => Ok(Self::Leading),
+      "trailing" => Ok(Self::Trailing),
+      "all" => Ok(Self::All),
+      other => Err(napi::Error::from_reason(format!(
+        "Unsupported ignore_whitespace mode: {other}"
+      ))),
+    }
+  }
+}
+
+fn is_space(b: u8) -> bool {
+  matches!(b, b' ' | b'\t' | b'\r' | b'\x0b' | b'\x0c')
+}
+
+/// Rewrite every line of `data` according to `mode`, keeping the line
+/// structure intact so line numbers and counts still refer to the original.
+/// Callers must run their binary guard first: this assumes text input.
+pub(crate) fn normalize_whitespace(data: &[u8], mode: IgnoreWhitespace) -> Cow<'_, [u8]> {
+  if mode == IgnoreWhitespace::None {
+    return Cow::Borrowed(data);
+  }
+
+  let mut out = Vec::with_capacity(data.len());
+  for line in data.split_inclusive(|&b| b == b'\n') {
+    let (body, newline) = match line.strip_suffix(b"\n") {
+      Some(body) => (body, true),
+      None => (line, false),
+    };
+    match mode {
+      IgnoreWhitespace::None => out.extend_from_slice(body),
+      IgnoreWhitespace::Leading => {
+        let start = body
+          .iter()
+          .position(|&b| !is_space(b))
+          .unwrap_or(body.len());
+        out.extend_from_slice(&body[start..]);
+      }
+      IgnoreWhitespace::Trailing => {
+        let end = body
+          .iter()
+          .rposition(|&b| !is_space(b))
+          .map_or(0, |i| i + 1);
+        out.extend_from_slice(&body[..end]);
+      }
+      IgnoreWhitespace::SpaceChange => {
+        let mut in_space = false;
+        for &b in body {
+          if is_space(b) {
+            in_space = true;
+          } else {
+            if in_space {
+              out.push(b' ');
+            }
+            in_space = false;
+            out.push(b);
+          }
+        }
+      }
+      IgnoreWhitespace::All => out.extend(body.iter().copied().filter(|&b| !is_space(b))),
+    }
+    if newline {
+      out.push(b'\n');
+    }
+  }
+  Cow::Owned(out)
+}
@@ -43,7 +43,16 @@ if (!process.env.TEAM_SERVER_URL) {
   process.exit(1);
 }
 
-const port = Number(process.env.RUNNER_PORT) || 3003;
+// RUNNER_PORT=0 binds any free port; the bound port is announced below.
+const rawPort = process.env.RUNNER_PORT;
+const port = rawPort ? Number(rawPort) : 3003;
+if ((rawPort && !/^\d+$/.test(rawPort)) || port > 65535) {
+  console.error(
+    `ERROR: RUNNER_PORT must be an integer between 0 and 65535, got "${rawPort}".\n` +
+      'Use RUNNER_PORT=0 to bind any free port.\n',
+  );
+  process.exit(1);
+}
 const host = process.env.RUNNER_HOST || '0.0.0.0';
 
 // Create the runtime app — stateless runner that proxies data to the server
@@ -61,10 +70,11 @@ if (prev) {
 // Initialize (service provider, handlers, team mode connection)
 await runtime.init();
 
+// No reusePort: a port already taken by another runner must fail startup
+// instead of silently sharing it. Watch restarts stop the old server first.
 const server = Bun.serve({
   port,
   hostname: host,
-  reusePort: true,
   fetch(req: Request) {
     return runtime.app.fetch(req);
   },
@@ -134,3 +144,5 @@ log.info(
     host,
   },
 );
+// Machine-readable port announcement parsed by the desktop shell (src-tauri/src/server.rs)
+console.log(`LISTENING_ON_PORT=${server.port}`);
@@ -1,3 +1,73 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 fn main() {
+    // Build metadata surfaced by `app_get_build_info`
+    let git_hash = git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=FUNNY_GIT_HASH={git_hash}");
+    println!("cargo:rustc-env=FUNNY_BUILD_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=FUNNY_BUILD_PROFILE={}",
+        std::env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string())
+    );
+    rerun_if_head_moves();
+
     tauri_build::build()
 }
+
+/// Trimmed stdout of a successful `git` invocation.
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+}
+
+/// Rebuild when the commit HEAD resolves to changes: on checkout (`HEAD`),
+/// on commit to the current branch (its loose ref), and on `git pack-refs`
+/// or fetches that only touch `packed-refs`. Paths come from
+/// `git rev-parse --git-path` so linked worktrees and `GIT_DIR` work too.
+///
+/// Cargo reruns the script on every build for paths that don't exist, so a
+/// branch that is only in `packed-refs` is watched through its directory,
+/// where the loose ref appears on the next commit.
+fn rerun_if_head_moves() {
+    println!("cargo:rerun-if-env-changed=GIT_DIR");
+    let git_path = |file: &str| git(&["rev-parse", "--git-path", file]).map(PathBuf::from);
+
+    if let Some(head) = git_path("HEAD") {
+        println!("cargo:rerun-if-changed={}", head.display());
+    }
+    if let Some(packed) = git_path("packed-refs").filter(|p| p.exists()) {
+        println!("cargo:rerun-if-changed={}", packed.display());
+    }
+    if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]).and_then(|r| git_path(&r)) {
+        let watched = match branch.parent() {
+            Some(dir) if !branch.exists() => dir.to_path_buf(),
+            _ => branch,
+        };
+        println!("cargo:rerun-if-changed={}", watched.display());
+    }
+}
+
+/// Today's UTC date as `YYYY-MM-DD` (civil-from-days, no date crate needed).
+fn build_date() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let z = secs.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}-{month:02}-{day:02}")
+}
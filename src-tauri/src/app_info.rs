@@ -0,0 +1,28 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Build metadata baked in at compile time by build.rs.
+#[derive(Serialize)]
+pub struct BuildInfo {
+    /// From tauri.conf.json, like `app_get_version`.
+    version: String,
+    git_hash: &'static str,
+    build_date: &'static str,
+    profile: &'static str,
+}
+
+/// App version from tauri.conf.json.
+#[tauri::command]
+pub fn app_get_version(app: AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+#[tauri::command]
+pub fn app_get_build_info(app: AppHandle) -> BuildInfo {
+    BuildInfo {
+        version: app.package_info().version.to_string(),
+        git_hash: env!("FUNNY_GIT_HASH"),
+        build_date: env!("FUNNY_BUILD_DATE"),
+        profile: env!("FUNNY_BUILD_PROFILE"),
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// Characters rejected in paths passed to the file manager. Arguments are not
+/// run through a shell, but `explorer.exe` does its own command-line parsing,
+/// so anything shell-like is refused outright.
+const FORBIDDEN_CHARS: &[char] = &[
+    ';', '&', '|', '`', '$', '<', '>', '"', '\'', '\n', '\r', '*', '?', '!',
+];
+
+/// Reveals `path` in the system file manager (Finder, Explorer, or the
+/// default Linux file manager via `xdg-open` on its parent directory).
+#[tauri::command]
+pub async fn open_in_file_manager(app: AppHandle, path: String) -> Result<(), String> {
+    if let Some(c) = path.chars().find(|c| FORBIDDEN_CHARS.contains(c)) {
+        return Err(format!("Path contains a disallowed character: {c:?}"));
+    }
+    let target = Path::new(&path);
+    if !target.exists() {
+        return Err(format!("Path does not exist: {path}"));
+    }
+
+    let shell = app.shell();
+    let command = if cfg!(target_os = "macos") {
+        shell.command("open").args(["-R", path.as_str()])
+    } else if cfg!(windows) {
+        shell.command("explorer.exe").arg(format!("/select,{path}"))
+    } else {
+        let dir = target.parent().unwrap_or(target);
+        shell
+            .command("xdg-open")
+            .arg(dir.to_string_lossy().to_string())
+    };
+
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to open file manager: {e}"))?;
+    Ok(())
+}
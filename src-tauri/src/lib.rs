@@ -1,48 +1,23 @@
+mod app_info;
+mod file_manager;
+mod preview;
 mod pty;
+mod server;
 
-use tauri::Manager;
-use tauri::WebviewUrl;
-use tauri::WebviewWindowBuilder;
-use tauri_plugin_shell::ShellExt;
-use tauri_plugin_shell::process::CommandChild;
-
-struct ServerProcess(std::sync::Mutex<Option<CommandChild>>);
-
-/// Opens the single preview browser window. If it already exists, focuses it.
-/// The window loads index.html with a flag so the frontend renders the preview UI.
-#[tauri::command]
-async fn open_preview(app: tauri::AppHandle) -> Result<(), String> {
-    let label = "preview-browser";
-
-    // If window already exists, focus it
-    if let Some(window) = app.get_webview_window(label) {
-        window.set_focus().map_err(|e| format!("{e}"))?;
-        return Ok(());
-    }
+use std::time::Duration;
 
-    WebviewWindowBuilder::new(
-        &app,
-        label,
-        WebviewUrl::App(std::path::PathBuf::from("index.html")),
-    )
-    .initialization_script("window.__PREVIEW_MODE__ = true;")
-    .title("Preview Browser")
-    .inner_size(1280.0, 800.0)
-    .min_inner_size(600.0, 400.0)
-    .center()
-    .build()
-    .map_err(|e| format!("{e}"))?;
+use tauri::Manager;
 
-    Ok(())
-}
+/// How long the server sidecar and PTY children get to exit on their own at
+/// app shutdown before being killed. Override with `FUNNY_SHUTDOWN_TIMEOUT_MS`.
+const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
 
-/// Closes the preview browser window.
-#[tauri::command]
-async fn close_preview(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("preview-browser") {
-        window.close().map_err(|e| format!("{e}"))?;
-    }
-    Ok(())
+fn shutdown_grace() -> Duration {
+    std::env::var("FUNNY_SHUTDOWN_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_SHUTDOWN_GRACE)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -50,27 +25,58 @@ pub fn run() {
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .manage(pty::PtyManager::new())
+        .manage(server::ServerLog::new())
+        .manage(server::ServerPort::from_env())
+        .manage(preview::PreviewWindows::new())
         .invoke_handler(tauri::generate_handler![
             pty::pty_spawn,
             pty::pty_write,
+            pty::pty_paste,
+            pty::pty_set_paused,
             pty::pty_resize,
             pty::pty_kill,
-            open_preview,
-            close_preview,
+            pty::pty_send_signal,
+            pty::list_pty_sessions,
+            pty::pty_set_flush_interval,
+            pty::pty_set_idle_interval,
+            pty::pty_get_history,
+            pty::pty_get_buffer,
+            pty::pty_get_plain_buffer,
+            pty::pty_list,
+            pty::pty_get_pid,
+            pty::pty_get_usage,
+            pty::pty_get_cwd,
+            pty::pty_wait,
+            pty::pty_start_recording,
+            pty::pty_stop_recording,
+            preview::open_preview,
+            preview::open_preview_url,
+            preview::open_preview_instance,
+            preview::list_preview_instances,
+            preview::close_preview,
+            preview::preview_navigate,
+            preview::preview_set_size,
+            preview::preview_reload,
+            preview::preview_toggle_devtools,
+            preview::preview_screenshot,
+            server::server_restart,
+            server::server_get_log,
+            server::server_health_check,
+            server::server_get_port,
+            server::server_status,
+            app_info::app_get_version,
+            app_info::app_get_build_info,
+            file_manager::open_in_file_manager,
         ])
         .setup(|app| {
             // Spawn the server sidecar on startup
-            let shell = app.shell();
-            let sidecar = shell
-                .sidecar("funny-server")
-                .expect("failed to create sidecar command");
-
-            let (_rx, child) = sidecar
-                .spawn()
-                .expect("failed to spawn server sidecar");
+            let child = server::spawn_sidecar(app.handle()).expect("failed to start server");
 
             // Store the child process so we can kill it on exit
-            app.manage(ServerProcess(std::sync::Mutex::new(Some(child))));
+            app.manage(server::ServerProcess(std::sync::Mutex::new(Some(child))));
+
+            // Restore saved preview window placement
+            app.manage(preview::PreviewWindowState::load(app.handle()));
 
             Ok(())
         })
@@ -78,20 +84,28 @@ pub fn run() {
         .expect("error while building tauri application");
 
     app.run(|app_handle, event| {
+        if let tauri::RunEvent::WindowEvent { label, event, .. } = &event {
+            preview::handle_window_event(app_handle, label, event);
+        }
+
         if let tauri::RunEvent::Exit = event {
-            // Kill all PTY instances
-            if let Some(pty_state) = app_handle.try_state::<pty::PtyManager>() {
-                pty::kill_all(&pty_state);
+            // Persist preview window placement
+            if let Some(state) = app_handle.try_state::<preview::PreviewWindowState>() {
+                let _ = state.save(app_handle);
             }
 
-            // Kill the server process on app exit
-            if let Some(state) = app_handle.try_state::<ServerProcess>() {
-                if let Ok(mut guard) = state.0.lock() {
-                    if let Some(child) = guard.take() {
-                        let _ = child.kill();
-                    }
-                }
+            let grace = shutdown_grace();
+
+            // Stop the server in parallel so both share the same grace period
+            let server_app = app_handle.clone();
+            let server_stop = std::thread::spawn(move || server::shutdown(&server_app, grace));
+
+            // Stop all PTY instances
+            if let Some(pty_state) = app_handle.try_state::<pty::PtyManager>() {
+                pty::kill_all(&pty_state, grace);
             }
+
+            let _ = server_stop.join();
         }
     });
 }
@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, LogicalSize, Manager, State, WebviewUrl, WebviewWindowBuilder, WindowEvent,
+};
+
+/// Id used when the frontend doesn't pass one, so existing zero-arg callers
+/// keep working against a single shared preview window.
+const DEFAULT_PREVIEW_ID: &str = "default";
+
+/// Prefix shared by every preview window label.
+const PREVIEW_LABEL_PREFIX: &str = "preview-browser-";
+
+/// File in the app data dir where preview geometry is persisted.
+const GEOMETRY_FILE: &str = "preview_geometry.json";
+
+/// Last known placement of a preview window, in logical pixels. While the
+/// window is maximized only `maximized` changes, so un-maximizing after a
+/// restart returns to the previous normal size.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    maximized: bool,
+}
+
+/// Minimum time between geometry writes while a window is being dragged or
+/// resized. The final placement is always written when the window closes.
+const SAVE_THROTTLE: Duration = Duration::from_secs(1);
+
+/// Saved geometry for each preview window, keyed by window label, plus the
+/// time of the last write to disk.
+pub struct PreviewWindowState(
+    pub Mutex<HashMap<String, WindowGeometry>>,
+    Mutex<Option<Instant>>,
+);
+
+impl PreviewWindowState {
+    /// Load previously persisted geometry, or start empty.
+    pub fn load(app: &AppHandle) -> Self {
+        let saved = geometry_path(app)
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self(Mutex::new(saved), Mutex::new(None))
+    }
+
+    /// Write the current geometry map to disk.
+    pub fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let path = geometry_path(app).ok_or("App data directory unavailable")?;
+        let data = {
+            let map = self.0.lock().map_err(|e| e.to_string())?;
+            serde_json::to_vec_pretty(&*map).map_err(|e| e.to_string())?
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, data).map_err(|e| e.to_string())?;
+        if let Ok(mut last) = self.1.lock() {
+            *last = Some(Instant::now());
+        }
+        Ok(())
+    }
+
+    /// Save unless a save happened within `SAVE_THROTTLE`.
+    fn save_throttled(&self, app: &AppHandle) {
+        let due = self
+            .1
+            .lock()
+            .map(|last| match *last {
+                Some(t) => t.elapsed() >= SAVE_THROTTLE,
+                None => true,
+            })
+            .unwrap_or(false);
+        if due {
+            let _ = self.save(app);
+        }
+    }
+}
+
+/// Fit saved geometry onto the monitors that are connected now. Returns
+/// `None` when the saved top-left corner isn't on any monitor (e.g. it was on
+/// a display that has since been disconnected), so the caller falls back to a
+/// centered default. Otherwise the window is shrunk to fit its monitor and
+/// nudged fully on-screen.
+fn clamp_to_monitors(app: &AppHandle, geometry: WindowGeometry) -> Option<WindowGeometry> {
+    let monitors = app.available_monitors().ok()?;
+    monitors.iter().find_map(|monitor| {
+        let scale = monitor.scale_factor();
+        let origin = monitor.position().to_logical::<f64>(scale);
+        let size = monitor.size().to_logical::<f64>(scale);
+        let inside = geometry.x >= origin.x
+            && geometry.x < origin.x + size.width
+            && geometry.y >= origin.y
+            && geometry.y < origin.y + size.height;
+        if !inside {
+            return None;
+        }
+        let width = geometry.width.min(size.width);
+        let height = geometry.height.min(size.height);
+        Some(WindowGeometry {
+            x: geometry.x.min(origin.x + size.width - width),
+            y: geometry.y.min(origin.y + size.height - height),
+            width,
+            height,
+            maximized: geometry.maximized,
+        })
+    })
+}
+
+/// Ids of the preview windows that are currently open. Entries are added when
+/// a window is built and removed when it is destroyed.
+pub struct PreviewWindows(pub Mutex<HashSet<String>>);
+
+impl PreviewWindows {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashSet::new()))
+    }
+}
+
+fn geometry_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(GEOMETRY_FILE))
+}
+
+/// Track moves/resizes of preview windows, persist the geometry once a
+/// preview window is destroyed, and drop it from `PreviewWindows`. Called from the app's run loop.
+pub fn handle_window_event(app: &AppHandle, label: &str, event: &WindowEvent) {
+    let Some(id) = label.strip_prefix(PREVIEW_LABEL_PREFIX) else {
+        return;
+    };
+    if let WindowEvent::Destroyed = event {
+        if let Some(windows) = app.try_state::<PreviewWindows>() {
+            if let Ok(mut set) = windows.0.lock() {
+                set.remove(id);
+            }
+        }
+    }
+    let Some(state) = app.try_state::<PreviewWindowState>() else {
+        return;
+    };
+
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            let Some(window) = app.get_webview_window(label) else {
+                return;
+            };
+            let maximized = window.is_maximized().unwrap_or(false);
+            let Ok(mut map) = state.0.lock() else {
+                return;
+            };
+            if maximized {
+                if let Some(geometry) = map.get_mut(label) {
+                    geometry.maximized = true;
+                }
+                drop(map);
+                state.save_throttled(app);
+                return;
+            }
+            let scale = window.scale_factor().unwrap_or(1.0);
+            let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+                return;
+            };
+            let position = position.to_logical::<f64>(scale);
+            let size = size.to_logical::<f64>(scale);
+            map.insert(
+                label.to_string(),
+                WindowGeometry {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                    maximized: false,
+                },
+            );
+            drop(map);
+            state.save_throttled(app);
+        }
+        WindowEvent::Destroyed => {
+            let _ = state.save(app);
+        }
+        _ => {}
+    }
+}
+
+/// Window label for the preview keyed by `id` (e.g. one per project).
+/// Tauri panics on labels with characters outside `[A-Za-z0-9-/:_]`, so the
+/// id is validated here and rejected with an error instead.
+fn preview_label(id: Option<&str>) -> Result<String, String> {
+    let id = id.unwrap_or(DEFAULT_PREVIEW_ID);
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '/' | ':' | '_'))
+    {
+        return Err(format!("Invalid preview id: {id}"));
+    }
+    Ok(format!("{PREVIEW_LABEL_PREFIX}{id}"))
+}
+
+/// Opens the preview browser window for `id`. If it already exists, focuses it.
+/// The window loads index.html with a flag so the frontend renders the preview UI,
+/// or `url` directly (e.g. the dev server) when given, avoiding a load-then-navigate
+/// flicker. An already-open window is navigated to `url`.
+#[tauri::command]
+pub async fn open_preview(
+    app: AppHandle,
+    id: Option<String>,
+    url: Option<String>,
+) -> Result<(), String> {
+    let label = preview_label(id.as_deref())?;
+    let url = url.as_deref().map(parse_preview_url).transpose()?;
+
+    // If window already exists, focus it
+    if let Some(window) = app.get_webview_window(&label) {
+        if let Some(url) = url {
+            window.navigate(url).map_err(|e| format!("{e}"))?;
+        }
+        window.set_focus().map_err(|e| format!("{e}"))?;
+        return Ok(());
+    }
+
+    let webview_url = match url {
+        Some(url) => WebviewUrl::External(url),
+        None => WebviewUrl::App(PathBuf::from("index.html")),
+    };
+    build_preview_window(&app, &label, webview_url, "window.__PREVIEW_MODE__ = true;")
+}
+
+/// Opens the preview window for `id` at `url_path` (an app route such as
+/// `/dashboard`), exposed to the frontend as `window.__PREVIEW_URL__`, which
+/// then renders the app on that route rather than the preview tab browser.
+/// If the window is already open it is focused and sent a `preview:navigate`
+/// event with the path, which its router follows.
+#[tauri::command]
+pub async fn open_preview_url(
+    app: AppHandle,
+    id: Option<String>,
+    url_path: String,
+) -> Result<(), String> {
+    // Only same-origin absolute paths: rejects `javascript:` and other
+    // schemes as well as protocol-relative `//host` URLs.
+    if !url_path.starts_with('/') || url_path.starts_with("//") {
+        return Err(format!("Invalid preview path: {url_path}"));
+    }
+    let label = preview_label(id.as_deref())?;
+
+    if let Some(window) = app.get_webview_window(&label) {
+        app.emit_to(&label, "preview:navigate", &url_path)
+            .map_err(|e| format!("{e}"))?;
+        window.set_focus().map_err(|e| format!("{e}"))?;
+        return Ok(());
+    }
+
+    let path_literal = serde_json::to_string(&url_path).map_err(|e| e.to_string())?;
+    let script =
+        format!("window.__PREVIEW_MODE__ = true; window.__PREVIEW_URL__ = {path_literal};");
+    build_preview_window(
+        &app,
+        &label,
+        WebviewUrl::App(PathBuf::from("index.html")),
+        &script,
+    )
+}
+
+/// Opens an additional preview window keyed by `instance_id` at `url_path`,
+/// so several URLs can be previewed side by side.
+#[tauri::command]
+pub async fn open_preview_instance(
+    app: AppHandle,
+    instance_id: String,
+    url_path: String,
+) -> Result<(), String> {
+    open_preview_url(app, Some(instance_id), url_path).await
+}
+
+/// Ids of all open preview windows, sorted.
+#[tauri::command]
+pub async fn list_preview_instances(
+    state: State<'_, PreviewWindows>,
+) -> Result<Vec<String>, String> {
+    let set = state.0.lock().map_err(|e| e.to_string())?;
+    let mut ids: Vec<String> = set.iter().cloned().collect();
+    ids.sort();
+    Ok(ids)
+}
+
+/// Create a preview window, restoring its saved geometry when available.
+fn build_preview_window(
+    app: &AppHandle,
+    label: &str,
+    url: WebviewUrl,
+    init_script: &str,
+) -> Result<(), String> {
+    let saved = app
+        .try_state::<PreviewWindowState>()
+        .and_then(|state| state.0.lock().ok()?.get(label).copied())
+        .and_then(|geometry| clamp_to_monitors(app, geometry));
+
+    let builder = WebviewWindowBuilder::new(app, label, url)
+        .initialization_script(init_script)
+        .title("Preview Browser")
+        .min_inner_size(600.0, 400.0);
+
+    let builder = match saved {
+        Some(geometry) => builder
+            .position(geometry.x, geometry.y)
+            .inner_size(geometry.width, geometry.height)
+            .maximized(geometry.maximized),
+        None => builder.inner_size(1280.0, 800.0).center(),
+    };
+
+    builder.build().map_err(|e| format!("{e}"))?;
+
+    if let (Some(windows), Some(id)) = (
+        app.try_state::<PreviewWindows>(),
+        label.strip_prefix(PREVIEW_LABEL_PREFIX),
+    ) {
+        if let Ok(mut set) = windows.0.lock() {
+            set.insert(id.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Closes the preview browser window for `id`.
+#[tauri::command]
+pub async fn close_preview(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    let label = preview_label(id.as_deref())?;
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close().map_err(|e| format!("{e}"))?;
+    }
+    Ok(())
+}
+
+/// URL schemes the preview may be pointed at. `tauri` is the app's own asset
+/// protocol (Windows serves it over `http://tauri.localhost` instead).
+const ALLOWED_SCHEMES: &[&str] = &["http", "https", "file", "tauri"];
+
+/// Loads `url` in an already-open preview window, e.g. to follow the dev
+/// server to a specific route. Errors if the window doesn't exist.
+#[tauri::command]
+pub async fn preview_navigate(
+    app: AppHandle,
+    id: Option<String>,
+    url: String,
+) -> Result<(), String> {
+    let label = preview_label(id.as_deref())?;
+    let window = app
+        .get_webview_window(&label)
+        .ok_or("Preview window is not open")?;
+
+    window
+        .navigate(parse_preview_url(&url)?)
+        .map_err(|e| format!("{e}"))
+}
+
+/// Parses a URL the preview is asked to load, rejecting schemes outside
+/// `ALLOWED_SCHEMES` (e.g. `javascript:`).
+fn parse_preview_url(url: &str) -> Result<tauri::Url, String> {
+    let parsed = tauri::Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    if !ALLOWED_SCHEMES.contains(&parsed.scheme()) {
+        return Err(format!("Unsupported URL scheme: {}", parsed.scheme()));
+    }
+    Ok(parsed)
+}
+
+/// Resizes the preview's content area to `width` x `height` logical pixels
+/// (window chrome comes on top), e.g. to snap to a responsive breakpoint.
+/// A maximized window is restored first so the size takes effect.
+#[tauri::command]
+pub async fn preview_set_size(
+    app: AppHandle,
+    id: Option<String>,
+    width: f64,
+    height: f64,
+) -> Result<(), String> {
+    if !(width.is_finite() && height.is_finite() && width >= 1.0 && height >= 1.0) {
+        return Err(format!("Invalid preview size: {width}x{height}"));
+    }
+    let label = preview_label(id.as_deref())?;
+    let window = app
+        .get_webview_window(&label)
+        .ok_or("Preview window is not open")?;
+
+    if window.is_maximized().unwrap_or(false) {
+        window.unmaximize().map_err(|e| format!("{e}"))?;
+    }
+    // `set_size` sizes the inner (content) area, not the outer frame.
+    window
+        .set_size(LogicalSize::new(width, height))
+        .map_err(|e| format!("{e}"))
+}
+
+/// Refetches the page and every resource it loaded with `cache: "reload"`,
+/// which replaces their HTTP cache entries, then reloads. Platform webviews
+/// have no cache-only purge, and clearing all browsing data would also wipe
+/// the previewed app's cookies and storage.
+const HARD_RELOAD_SCRIPT: &str = r#"(() => {
+  const urls = [location.href, ...performance.getEntriesByType("resource").map((e) => e.name)];
+  Promise.allSettled(urls.map((url) => fetch(url, { cache: "reload", mode: "no-cors", credentials: "include" })))
+    .finally(() => location.reload());
+})()"#;
+
+/// Reloads the preview window. With `ignore_cache`, the page and its
+/// resources are refetched from the network first, bypassing the HTTP cache
+/// while leaving cookies and storage alone.
+/// Does nothing if the window isn't open, since this is bound to a global
+/// shortcut that may fire with no preview showing.
+#[tauri::command]
+pub async fn preview_reload(
+    app: AppHandle,
+    id: Option<String>,
+    ignore_cache: Option<bool>,
+) -> Result<(), String> {
+    let label = preview_label(id.as_deref())?;
+    let Some(window) = app.get_webview_window(&label) else {
+        return Ok(());
+    };
+
+    let script = if ignore_cache.unwrap_or(false) {
+        HARD_RELOAD_SCRIPT
+    } else {
+        "window.location.reload()"
+    };
+    window.eval(script).map_err(|e| e.to_string())
+}
+
+/// Opens or closes devtools for the preview window. Only available in debug
+/// builds, or in release builds compiled with the `devtools` feature.
+#[tauri::command]
+pub async fn preview_toggle_devtools(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    let label = preview_label(id.as_deref())?;
+    let window = app
+        .get_webview_window(&label)
+        .ok_or("Preview window is not open")?;
+
+    #[cfg(any(debug_assertions, feature = "devtools"))]
+    {
+        if window.is_devtools_open() {
+            window.close_devtools();
+        } else {
+            window.open_devtools();
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "devtools")))]
+    {
+        let _ = window;
+        Err("Devtools are disabled in this build".to_string())
+    }
+}
+
+/// Captures the preview window's current contents as PNG (default) or JPEG.
+/// With `path` the image is written there and an empty response returned;
+/// otherwise the encoded bytes are returned as a binary IPC response.
+///
+/// Platform webviews offer no capture API, so this grabs the window's
+/// on-screen region from its monitor — the window must be visible and not
+/// covered by other windows for the capture to be accurate.
+#[tauri::command]
+pub async fn preview_screenshot(
+    app: AppHandle,
+    id: Option<String>,
+    format: Option<String>,
+    path: Option<String>,
+) -> Result<tauri::ipc::Response, String> {
+    let label = preview_label(id.as_deref())?;
+    let window = app
+        .get_webview_window(&label)
+        .ok_or("Preview window is not open")?;
+
+    let image_format = match format.as_deref().unwrap_or("png") {
+        "png" => image::ImageFormat::Png,
+        "jpeg" | "jpg" => image::ImageFormat::Jpeg,
+        other => return Err(format!("Unsupported screenshot format: {other}")),
+    };
+
+    let position = window.inner_position().map_err(|e| format!("{e}"))?;
+    let size = window.inner_size().map_err(|e| format!("{e}"))?;
+
+    let monitor = xcap::Monitor::from_point(position.x, position.y).map_err(|e| e.to_string())?;
+    let origin_x = monitor.x().map_err(|e| e.to_string())?;
+    let origin_y = monitor.y().map_err(|e| e.to_string())?;
+    let capture = monitor.capture_image().map_err(|e| e.to_string())?;
+
+    // Crop the window's inner area out of the monitor capture
+    let crop_x = (position.x - origin_x).max(0) as u32;
+    let crop_y = (position.y - origin_y).max(0) as u32;
+    let width = size.width.min(capture.width().saturating_sub(crop_x));
+    let height = size.height.min(capture.height().saturating_sub(crop_y));
+    if width == 0 || height == 0 {
+        return Err("Preview window is not on screen".to_string());
+    }
+    let cropped = image::imageops::crop_imm(&capture, crop_x, crop_y, width, height).to_image();
+
+    // JPEG has no alpha channel
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    match image_format {
+        image::ImageFormat::Jpeg => image::DynamicImage::ImageRgba8(cropped)
+            .to_rgb8()
+            .write_to(&mut encoded, image_format),
+        _ => cropped.write_to(&mut encoded, image_format),
+    }
+    .map_err(|e| e.to_string())?;
+    let bytes = encoded.into_inner();
+
+    match path {
+        Some(path) => {
+            std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+            Ok(tauri::ipc::Response::new(Vec::new()))
+        }
+        None => Ok(tauri::ipc::Response::new(bytes)),
+    }
+}
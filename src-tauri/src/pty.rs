@@ -1,34 +1,856 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
-use serde::Serialize;
-use std::collections::HashMap;
-use std::io::{Read, Write};
-use std::sync::Mutex;
-use tauri::{AppHandle, Emitter, State};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 type TerminalId = String;
 
 struct PtyInstance {
-    #[allow(dead_code)]
-    child: Box<dyn portable_pty::Child + Send + Sync>,
+    killer: Box<dyn portable_pty::ChildKiller + Send + Sync>,
+    exit: Arc<ExitSignal>,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
+    cwd: String,
+    title: String,
+    pid: u32,
+    history: Arc<Mutex<OutputHistory>>,
+    /// Last-known grid size, from `pty_spawn` or the latest `pty_resize`.
+    rows: u16,
+    cols: u16,
+    recording: Arc<Mutex<Option<CastRecorder>>>,
+    /// Whether the program has enabled bracketed paste (`ESC [ ? 2004 h`),
+    /// tracked by the reader thread from the output stream.
+    bracketed_paste: Arc<AtomicBool>,
+    pause: Arc<PauseGate>,
+}
+
+/// Exit code of a PTY child, set once by its exit watcher thread. This is the
+/// single source of truth for both the `pty:exit:<id>` event and `pty_wait`,
+/// and any number of waiters can block on it concurrently.
+#[derive(Default)]
+struct ExitSignal {
+    code: Mutex<Option<u32>>,
+    cond: Condvar,
+}
+
+impl ExitSignal {
+    fn set(&self, code: u32) {
+        if let Ok(mut guard) = self.code.lock() {
+            if guard.is_none() {
+                *guard = Some(code);
+                self.cond.notify_all();
+            }
+        }
+    }
+
+    fn get(&self) -> Option<u32> {
+        self.code.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// Block until the child has exited and return its exit code.
+    fn wait(&self) -> u32 {
+        let mut guard = match self.code.lock() {
+            Ok(g) => g,
+            Err(_) => return 1,
+        };
+        loop {
+            if let Some(code) = *guard {
+                return code;
+            }
+            guard = match self.cond.wait(guard) {
+                Ok(g) => g,
+                Err(_) => return 1,
+            };
+        }
+    }
+}
+
+/// Flow control for a PTY's reader thread, toggled by `pty_set_paused`.
+/// While paused the reader stops draining the master, so the kernel buffer
+/// fills up and the child blocks on its next write.
+#[derive(Default)]
+struct PauseGate {
+    /// `(paused, closed)`. Once closed, the gate never pauses again.
+    state: Mutex<(bool, bool)>,
+    cond: Condvar,
+}
+
+impl PauseGate {
+    fn set(&self, paused: bool) {
+        if let Ok(mut guard) = self.state.lock() {
+            guard.0 = paused && !guard.1;
+            self.cond.notify_all();
+        }
+    }
+
+    /// Release the reader for good once the child has exited, so it can
+    /// drain the remaining output and finish.
+    fn close(&self) {
+        if let Ok(mut guard) = self.state.lock() {
+            *guard = (false, true);
+            self.cond.notify_all();
+        }
+    }
+
+    /// Block while the gate is paused.
+    fn wait(&self) {
+        let mut guard = match self.state.lock() {
+            Ok(g) => g,
+            Err(_) => return,
+        };
+        while guard.0 {
+            guard = match self.cond.wait(guard) {
+                Ok(g) => g,
+                Err(_) => return,
+            };
+        }
+    }
 }
 
 pub struct PtyManager {
     instances: Mutex<HashMap<TerminalId, PtyInstance>>,
+    /// Output coalescing window in milliseconds, shared with every batcher
+    /// thread so changes apply to running terminals on their next batch.
+    flush_interval_ms: Arc<AtomicU64>,
+    /// Quiet period in milliseconds after which a terminal is reported idle,
+    /// shared with every activity monitor thread like `flush_interval_ms`.
+    idle_interval_ms: Arc<AtomicU64>,
 }
 
 impl PtyManager {
     pub fn new() -> Self {
         Self {
             instances: Mutex::new(HashMap::new()),
+            flush_interval_ms: Arc::new(AtomicU64::new(DEFAULT_FLUSH_INTERVAL_MS)),
+            idle_interval_ms: Arc::new(AtomicU64::new(DEFAULT_IDLE_INTERVAL_MS)),
         }
     }
 }
 
+/// Default number of output lines kept per terminal for `pty_get_history`.
+const DEFAULT_HISTORY_LINES: usize = 4096;
+
+/// Default and hard cap on buffered history bytes per terminal, independent
+/// of the line limit, so a program printing huge lines can't grow the buffer
+/// unbounded. `PtySpawnOptions::history_bytes` can only lower it.
+const MAX_HISTORY_BYTES: usize = 2 * 1024 * 1024;
+
+/// How far past the byte limit eviction looks for a line end to cut at.
+/// Without one in reach (a runaway line) the history is cut mid-line.
+const LINE_BOUNDARY_SLACK: usize = 16 * 1024;
+
+/// Bounded ring buffer of raw (ANSI-escaped) output chunks. When either the
+/// line or byte limit is exceeded, the oldest output is dropped first, up to
+/// the end of a line where possible.
+struct OutputHistory {
+    chunks: VecDeque<Vec<u8>>,
+    total_bytes: usize,
+    total_lines: usize,
+    max_lines: usize,
+    max_bytes: usize,
+    /// Bytes evicted since the terminal was spawned.
+    dropped_bytes: u64,
+}
+
+impl OutputHistory {
+    fn new(max_lines: usize, max_bytes: usize) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            total_bytes: 0,
+            total_lines: 0,
+            max_lines,
+            max_bytes,
+            dropped_bytes: 0,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.total_bytes += chunk.len();
+        self.total_lines += count_newlines(chunk);
+        self.chunks.push_back(chunk.to_vec());
+
+        let excess_bytes = self.total_bytes.saturating_sub(self.max_bytes);
+        let excess_lines = self.total_lines.saturating_sub(self.max_lines);
+        if excess_bytes > 0 || excess_lines > 0 {
+            let cut = self.cut_point(excess_bytes, excess_lines);
+            self.drop_front(cut);
+        }
+    }
+
+    /// Number of leading bytes to evict: at least `min_bytes` and `min_lines`
+    /// whole lines, extended to the end of the line the cut falls in. When no
+    /// line ends within `LINE_BOUNDARY_SLACK`, cut at the first character
+    /// boundary after `min_bytes` instead.
+    fn cut_point(&self, min_bytes: usize, min_lines: usize) -> usize {
+        let mut char_boundary = None;
+        let mut lines = 0;
+        for (offset, &b) in self.chunks.iter().flatten().enumerate() {
+            if char_boundary.is_none() && offset >= min_bytes && (b & 0xc0) != 0x80 {
+                char_boundary = Some(offset);
+            }
+            if lines >= min_lines && offset >= min_bytes + LINE_BOUNDARY_SLACK {
+                break;
+            }
+            if b == b'\n' {
+                lines += 1;
+                if lines >= min_lines && offset >= min_bytes {
+                    return offset + 1;
+                }
+            }
+        }
+        char_boundary.unwrap_or(self.total_bytes)
+    }
+
+    fn drop_front(&mut self, mut n: usize) {
+        self.dropped_bytes += n as u64;
+        while n > 0 {
+            let Some(front) = self.chunks.front_mut() else {
+                break;
+            };
+            let take = n.min(front.len());
+            self.total_bytes -= take;
+            self.total_lines -= count_newlines(&front[..take]);
+            if take == front.len() {
+                self.chunks.pop_front();
+            } else {
+                front.drain(..take);
+            }
+            n -= take;
+        }
+    }
+
+    fn contents(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.total_bytes);
+        for chunk in &self.chunks {
+            out.extend_from_slice(chunk);
+        }
+        out
+    }
+}
+
+/// Writes PTY output to an asciicast v2 file: a JSON header line followed by
+/// one `[elapsed, "o", data]` line per output chunk, with `elapsed` in
+/// seconds relative to the start of the recording.
+struct CastRecorder {
+    writer: BufWriter<File>,
+    started: Instant,
+    pending: Vec<u8>,
+}
+
+impl CastRecorder {
+    fn create(path: &str, cols: u16, rows: u16) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": timestamp,
+        });
+        writeln!(writer, "{}", header)?;
+        Ok(Self {
+            writer,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    fn write_output(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.pending.extend_from_slice(data);
+        let text = take_utf8_prefix(&mut self.pending);
+        self.write_event(&text)
+    }
+
+    fn write_event(&mut self, text: &str) -> std::io::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let line = serde_json::to_string(&(elapsed, "o", text))?;
+        writeln!(self.writer, "{}", line)
+    }
+
+    /// Write out any buffered partial character and flush the file.
+    fn finish(mut self) -> std::io::Result<()> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.pending)).to_string();
+        self.write_event(&rest)?;
+        self.writer.flush()
+    }
+}
+
+fn count_newlines(data: &[u8]) -> usize {
+    data.iter().filter(|&&b| b == b'\n').count()
+}
+
+/// Default window over which PTY output is coalesced into a single event.
+const DEFAULT_FLUSH_INTERVAL_MS: u64 = 12;
+
+/// Allowed range for `pty_set_flush_interval`.
+const MIN_FLUSH_INTERVAL_MS: u64 = 1;
+const MAX_FLUSH_INTERVAL_MS: u64 = 100;
+
+/// Flush as soon as a batch reaches this size, regardless of the window,
+/// so a flood of output never sits in memory for long.
+const MAX_BATCH_BYTES: usize = 64 * 1024;
+
+/// Largest payload of a single `pty://output/<id>` event, before base64.
+const MAX_OUTPUT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Chunks at or below this size that arrive after an idle period are emitted
+/// immediately — this is the keystroke-echo path and must not wait a window.
+const SMALL_CHUNK_BYTES: usize = 256;
+
+/// Default quiet period after which a terminal is reported idle.
+const DEFAULT_IDLE_INTERVAL_MS: u64 = 750;
+
+/// Allowed range for `pty_set_idle_interval`.
+const MIN_IDLE_INTERVAL_MS: u64 = 100;
+const MAX_IDLE_INTERVAL_MS: u64 = 60_000;
+
+/// Output an idle terminal must produce before it is reported active again.
+/// Keystroke echo and a redrawn prompt stay below it, so a trickle of small
+/// writes doesn't flap between the two states.
+const ACTIVITY_MIN_BYTES: usize = 256;
+
+/// Optional settings for `pty_spawn`.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySpawnOptions {
+    /// Extra environment variables merged over the inherited environment;
+    /// provided values take precedence (e.g. `TERM`, `COLORTERM`).
+    env: Option<HashMap<String, String>>,
+    /// Lines of output kept for `pty_get_history` (default 4096).
+    history_lines: Option<usize>,
+    /// Bytes of output kept for `pty_get_history`; capped at (and defaulting
+    /// to) 2 MiB whatever is passed.
+    history_bytes: Option<usize>,
+}
+
+/// Snapshot of an open terminal, returned by `list_pty_sessions` so the
+/// frontend can rebuild its tab list after a reload.
+#[derive(Clone, Serialize)]
+pub struct PtySessionInfo {
+    id: String,
+    cwd: String,
+    title: String,
+    pid: u32,
+}
+
+/// Liveness and geometry of a terminal, returned by `pty_list` so session
+/// restore can reattach to running PTYs at their previous grid size.
+#[derive(Clone, Serialize)]
+pub struct PtyInfo {
+    id: String,
+    pid: u32,
+    alive: bool,
+    cols: u16,
+    rows: u16,
+    /// Current working directory of the shell, or its last known one once
+    /// the process has exited.
+    cwd: String,
+}
+
+/// Identity of a terminal, returned by `pty_spawn`.
 #[derive(Clone, Serialize)]
-struct PtyDataPayload {
+pub struct PtySpawnResult {
+    id: String,
+    /// OS process id of the shell; on Windows, the process hosted by the
+    /// ConPTY. 0 if the platform didn't report one.
+    pid: u32,
+}
+
+/// Buffered output of a terminal, returned by `pty_get_buffer`.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyBuffer {
     data: String,
+    /// Output evicted from the front of the buffer so far; non-zero means
+    /// `data` no longer starts at the beginning of the session.
+    dropped_bytes: u64,
+}
+
+/// Buffered output of a terminal as plain text, returned by
+/// `pty_get_plain_buffer`.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyPlainBuffer {
+    text: String,
+    /// Offset into `text` where each line starts, indexed by line number and
+    /// counted in UTF-16 code units like JavaScript string indices, so a
+    /// search hit can be mapped back to its row with a binary search.
+    line_starts: Vec<usize>,
+}
+
+/// CPU and memory use of a terminal's process tree (the shell and all its
+/// descendants), returned by `pty_get_usage`.
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyUsage {
+    /// CPU time used during the sampling interval, as a percentage of one
+    /// core like `top` reports it, so a busy tree can exceed 100.
+    cpu_percent: f64,
+    /// Resident memory, summed over the tree.
+    memory_bytes: u64,
+    process_count: u32,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PtyExitPayload {
+    exit_code: u32,
+}
+
+#[derive(Clone, Serialize)]
+struct PtyTitlePayload {
+    id: String,
+    title: String,
+}
+
+/// Payload of events that only identify the terminal.
+#[derive(Clone, Serialize)]
+struct PtyIdPayload {
+    id: String,
+}
+
+/// Minimum interval between two `pty://title` events for the same terminal.
+/// Spinners that retitle on every frame are coalesced to the latest title.
+const TITLE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Upper bound on a buffered OSC body. Anything longer is not a title we care
+/// about, so the sequence is dropped instead of growing the buffer forever.
+const MAX_OSC_LEN: usize = 4096;
+
+#[derive(Clone, Copy, PartialEq)]
+enum OscState {
+    Ground,
+    Escape,
+    Body,
+    BodyEscape,
+}
+
+/// Incremental scanner for OSC 0/2 title sequences (`ESC ] 0 ; title BEL` or
+/// `ESC ] 2 ; title ESC \`). State is kept between `feed` calls so sequences
+/// split across PTY reads are still recognized.
+struct OscTitleParser {
+    state: OscState,
+    body: Vec<u8>,
+}
+
+impl OscTitleParser {
+    fn new() -> Self {
+        Self {
+            state: OscState::Ground,
+            body: Vec::new(),
+        }
+    }
+
+    /// Feed a chunk of raw PTY output. Returns the last title completed in
+    /// this chunk, if any.
+    fn feed(&mut self, data: &[u8]) -> Option<String> {
+        let mut title = None;
+        for &b in data {
+            match self.state {
+                OscState::Ground => {
+                    if b == 0x1b {
+                        self.state = OscState::Escape;
+                    }
+                }
+                OscState::Escape => {
+                    if b == b']' {
+                        self.body.clear();
+                        self.state = OscState::Body;
+                    } else if b != 0x1b {
+                        self.state = OscState::Ground;
+                    }
+                }
+                OscState::Body => match b {
+                    0x07 => {
+                        title = self.finish().or(title);
+                    }
+                    0x1b => self.state = OscState::BodyEscape,
+                    _ => {
+                        if self.body.len() >= MAX_OSC_LEN {
+                            self.body.clear();
+                            self.state = OscState::Ground;
+                        } else {
+                            self.body.push(b);
+                        }
+                    }
+                },
+                OscState::BodyEscape => {
+                    if b == b'\\' {
+                        title = self.finish().or(title);
+                    } else {
+                        // Unterminated OSC followed by a new escape sequence.
+                        self.body.clear();
+                        self.state = if b == b']' {
+                            OscState::Body
+                        } else {
+                            OscState::Ground
+                        };
+                    }
+                }
+            }
+        }
+        title
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        self.state = OscState::Ground;
+        let body = std::mem::take(&mut self.body);
+        let (kind, text) = match body.iter().position(|&b| b == b';') {
+            Some(idx) => (&body[..idx], &body[idx + 1..]),
+            None => return None,
+        };
+        match kind {
+            b"0" | b"2" => Some(String::from_utf8_lossy(text).to_string()),
+            _ => None,
+        }
+    }
+}
+
+const BRACKETED_PASTE_ON: &[u8] = b"\x1b[?2004h";
+const BRACKETED_PASTE_OFF: &[u8] = b"\x1b[?2004l";
+
+/// Markers wrapped around pasted text while bracketed paste is enabled, so
+/// the shell inserts it verbatim instead of executing it line by line.
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Watches PTY output for bracketed-paste mode switches. The last few bytes
+/// of each chunk are kept so a sequence split across reads is still seen.
+struct BracketedPasteTracker {
+    tail: Vec<u8>,
+}
+
+impl BracketedPasteTracker {
+    fn new() -> Self {
+        Self { tail: Vec::new() }
+    }
+
+    /// Feed a chunk of raw PTY output. Returns the mode set by the last
+    /// switch in this chunk, if any.
+    fn feed(&mut self, data: &[u8]) -> Option<bool> {
+        let mut window = std::mem::take(&mut self.tail);
+        window.extend_from_slice(data);
+
+        let last = |needle: &[u8]| window.windows(needle.len()).rposition(|w| w == needle);
+        let mode = match (last(BRACKETED_PASTE_ON), last(BRACKETED_PASTE_OFF)) {
+            (Some(on), Some(off)) => Some(on > off),
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        };
+
+        // Keep one byte short of a full sequence: anything longer was already
+        // matched above and must not be reported again on the next chunk.
+        let keep = BRACKETED_PASTE_ON.len() - 1;
+        self.tail = window[window.len().saturating_sub(keep)..].to_vec();
+        mode
+    }
+}
+
+/// Bells rung within this window of the last `pty://bell` event are folded
+/// into it, so a burst of BELs notifies once.
+const BELL_COALESCE: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, PartialEq)]
+enum BellState {
+    Ground,
+    Escape,
+    /// Inside an OSC, DCS, APC, PM or SOS string, where BEL is a terminator.
+    String,
+    StringEscape,
+}
+
+/// Strips bells (BEL outside of escape strings) from PTY output. A BEL that
+/// terminates an OSC sequence such as a title is left alone. State is kept
+/// between `feed` calls so sequences split across reads are handled.
+struct BellFilter {
+    state: BellState,
+}
+
+impl BellFilter {
+    fn new() -> Self {
+        Self {
+            state: BellState::Ground,
+        }
+    }
+
+    /// Returns `data` without its bells, and whether it contained any.
+    fn feed(&mut self, data: &[u8]) -> (Vec<u8>, bool) {
+        let mut out = Vec::with_capacity(data.len());
+        let mut rang = false;
+        for &b in data {
+            match (self.state, b) {
+                (BellState::Ground | BellState::Escape, 0x07) => {
+                    rang = true;
+                    continue;
+                }
+                (BellState::Ground, 0x1b) => self.state = BellState::Escape,
+                (BellState::Escape, b']' | b'P' | b'_' | b'^' | b'X') => {
+                    self.state = BellState::String
+                }
+                (BellState::Escape, 0x1b) => {}
+                (BellState::Escape, _) => self.state = BellState::Ground,
+                (BellState::String, 0x07) => self.state = BellState::Ground,
+                (BellState::String, 0x1b) => self.state = BellState::StringEscape,
+                (BellState::StringEscape, b'\\') => self.state = BellState::Ground,
+                (BellState::StringEscape, 0x1b) => {}
+                (BellState::StringEscape, b']' | b'P' | b'_' | b'^' | b'X') => {
+                    self.state = BellState::String
+                }
+                (BellState::StringEscape, _) => self.state = BellState::Ground,
+                _ => {}
+            }
+            out.push(b);
+        }
+        (out, rang)
+    }
+}
+
+/// Columns between tab stops, for expanding tabs in the plain-text view.
+const TAB_WIDTH: usize = 8;
+
+#[derive(Clone, Copy, PartialEq)]
+enum PlainState {
+    Ground,
+    Escape,
+    /// After ESC and one or more intermediate bytes (e.g. `ESC ( B`).
+    EscapeIntermediate,
+    Csi,
+    /// Inside an OSC, DCS, APC, PM or SOS string.
+    String,
+    StringEscape,
+}
+
+/// Renders raw PTY output as plain text, following the states of a VT
+/// parser: escape, CSI and string sequences are dropped, `\r`, backspace and
+/// `ESC [ K` overwrite or erase within the current line as on screen, tabs
+/// are expanded to spaces and other control characters are removed.
+struct PlainTextBuilder {
+    state: PlainState,
+    csi_params: String,
+    text: String,
+    /// UTF-16 offset into `text` of the start of every line.
+    line_starts: Vec<usize>,
+    utf16_len: usize,
+    line: Vec<char>,
+    cursor: usize,
+}
+
+impl PlainTextBuilder {
+    fn new() -> Self {
+        Self {
+            state: PlainState::Ground,
+            csi_params: String::new(),
+            text: String::new(),
+            line_starts: vec![0],
+            utf16_len: 0,
+            line: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    fn feed(&mut self, data: &str) {
+        for c in data.chars() {
+            self.advance(c);
+        }
+    }
+
+    fn advance(&mut self, c: char) {
+        match self.state {
+            PlainState::Ground => match c {
+                '\x1b' => self.state = PlainState::Escape,
+                c if c.is_control() => self.control(c),
+                c => self.print(c),
+            },
+            PlainState::Escape | PlainState::EscapeIntermediate => match c {
+                '[' if self.state == PlainState::Escape => {
+                    self.csi_params.clear();
+                    self.state = PlainState::Csi;
+                }
+                ']' | 'P' | 'X' | '^' | '_' if self.state == PlainState::Escape => {
+                    self.state = PlainState::String
+                }
+                '\x1b' => self.state = PlainState::Escape,
+                '\x20'..='\x2f' => self.state = PlainState::EscapeIntermediate,
+                c if c.is_control() => self.control(c),
+                _ => self.state = PlainState::Ground,
+            },
+            PlainState::Csi => match c {
+                '\x1b' => self.state = PlainState::Escape,
+                '\x20'..='\x3f' => self.csi_params.push(c),
+                '\x40'..='\x7e' => {
+                    if c == 'K' {
+                        self.erase_in_line();
+                    }
+                    self.state = PlainState::Ground;
+                }
+                c if c.is_control() => self.control(c),
+                _ => self.state = PlainState::Ground,
+            },
+            PlainState::String => match c {
+                '\x07' => self.state = PlainState::Ground,
+                '\x1b' => self.state = PlainState::StringEscape,
+                _ => {}
+            },
+            PlainState::StringEscape => {
+                if c == '\\' {
+                    self.state = PlainState::Ground;
+                } else {
+                    // Unterminated string followed by a new escape sequence.
+                    self.state = PlainState::Escape;
+                    self.advance(c);
+                }
+            }
+        }
+    }
+
+    fn print(&mut self, c: char) {
+        if self.cursor < self.line.len() {
+            self.line[self.cursor] = c;
+        } else {
+            self.line.push(c);
+        }
+        self.cursor += 1;
+    }
+
+    fn control(&mut self, c: char) {
+        match c {
+            '\n' => self.end_line(true),
+            '\r' => self.cursor = 0,
+            '\x08' => self.cursor = self.cursor.saturating_sub(1),
+            '\t' => {
+                self.cursor = (self.cursor / TAB_WIDTH + 1) * TAB_WIDTH;
+                while self.line.len() < self.cursor {
+                    self.line.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `ESC [ K` (to the end of the line) and `ESC [ 2 K` (the whole line);
+    /// erasing to the start of the line is rare enough to ignore.
+    fn erase_in_line(&mut self) {
+        match self.csi_params.as_str() {
+            "" | "0" => self.line.truncate(self.cursor),
+            "2" => self.line.clear(),
+            _ => {}
+        }
+    }
+
+    fn end_line(&mut self, newline: bool) {
+        for c in self.line.drain(..) {
+            self.text.push(c);
+            self.utf16_len += c.len_utf16();
+        }
+        self.cursor = 0;
+        if newline {
+            self.text.push('\n');
+            self.utf16_len += 1;
+            self.line_starts.push(self.utf16_len);
+        }
+    }
+
+    fn finish(mut self) -> PtyPlainBuffer {
+        self.end_line(false);
+        PtyPlainBuffer {
+            text: self.text,
+            line_starts: self.line_starts,
+        }
+    }
+}
+
+/// Spawn a thread that forwards title changes to the frontend, emitting at
+/// most one `pty://title` event per `TITLE_DEBOUNCE` window. Exits once the
+/// sender is dropped, flushing the last pending title first.
+fn spawn_title_emitter(app: AppHandle, id: TerminalId) -> mpsc::Sender<String> {
+    let (tx, rx) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        let mut last_emitted: Option<String> = None;
+        while let Ok(mut title) = rx.recv() {
+            let deadline = Instant::now() + TITLE_DEBOUNCE;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match rx.recv_timeout(remaining) {
+                    Ok(next) => title = next,
+                    Err(_) => break,
+                }
+            }
+            if last_emitted.as_deref() != Some(title.as_str()) {
+                if let Ok(mut instances) = app.state::<PtyManager>().instances.lock() {
+                    if let Some(instance) = instances.get_mut(&id) {
+                        instance.title = title.clone();
+                    }
+                }
+                let _ = app.emit(
+                    "pty://title",
+                    PtyTitlePayload {
+                        id: id.clone(),
+                        title: title.clone(),
+                    },
+                );
+                last_emitted = Some(title);
+            }
+        }
+    });
+    tx
+}
+
+/// Spawn a thread that reports when a terminal starts and stops producing
+/// output. The reader sends the size of every chunk it reads; `pty://idle`
+/// is emitted once nothing has arrived for the idle interval, and
+/// `pty://activity` once an idle terminal has produced `ACTIVITY_MIN_BYTES`
+/// without falling quiet again. Terminals start out idle. Exits once the
+/// sender is dropped.
+fn spawn_activity_monitor(
+    app: AppHandle,
+    id: TerminalId,
+    idle_interval_ms: Arc<AtomicU64>,
+) -> mpsc::Sender<usize> {
+    let (tx, rx) = mpsc::channel::<usize>();
+    std::thread::spawn(move || {
+        let mut active = false;
+        let mut pending_bytes = 0;
+        loop {
+            let interval = Duration::from_millis(idle_interval_ms.load(Ordering::Relaxed));
+            match rx.recv_timeout(interval) {
+                Ok(n) => {
+                    if active {
+                        continue;
+                    }
+                    pending_bytes += n;
+                    if pending_bytes >= ACTIVITY_MIN_BYTES {
+                        active = true;
+                        pending_bytes = 0;
+                        let _ = app.emit("pty://activity", PtyIdPayload { id: id.clone() });
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    pending_bytes = 0;
+                    if active {
+                        active = false;
+                        let _ = app.emit("pty://idle", PtyIdPayload { id: id.clone() });
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+    tx
 }
 
 #[tauri::command]
@@ -39,12 +861,18 @@ pub fn pty_spawn(
     cwd: String,
     rows: u16,
     cols: u16,
-) -> Result<(), String> {
+    options: Option<PtySpawnOptions>,
+) -> Result<PtySpawnResult, String> {
+    let options = options.unwrap_or_default();
+
     // Check if terminal already exists (idempotency)
     {
         let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        if instances.contains_key(&id) {
-            return Ok(());
+        if let Some(instance) = instances.get(&id) {
+            return Ok(PtySpawnResult {
+                id,
+                pid: instance.pid,
+            });
         }
     }
 
@@ -68,10 +896,42 @@ pub fn pty_spawn(
 
     let mut cmd = CommandBuilder::new(&shell_path);
     cmd.cwd(&cwd);
+    if let Some(env) = &options.env {
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+    }
 
-    let child = pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+    let mut child = pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+    let pid = child.process_id().unwrap_or(0);
+    let killer = child.clone_killer();
     let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
-    let mut reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
+    let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
+    let history_lines = options.history_lines.unwrap_or(DEFAULT_HISTORY_LINES);
+    let history_bytes = options
+        .history_bytes
+        .unwrap_or(MAX_HISTORY_BYTES)
+        .clamp(1, MAX_HISTORY_BYTES);
+    let history = Arc::new(Mutex::new(OutputHistory::new(
+        history_lines.max(1),
+        history_bytes,
+    )));
+    let recording: Arc<Mutex<Option<CastRecorder>>> = Arc::new(Mutex::new(None));
+    let exit = Arc::new(ExitSignal::default());
+    let bracketed_paste = Arc::new(AtomicBool::new(false));
+    let pause = Arc::new(PauseGate::default());
+
+    // Exit watcher: owns the child and records its exit code once it
+    // terminates, then releases a paused reader
+    {
+        let exit = exit.clone();
+        let pause = pause.clone();
+        std::thread::spawn(move || {
+            let code = child.wait().map(|status| status.exit_code()).unwrap_or(1);
+            exit.set(code);
+            pause.close();
+        });
+    }
 
     // Store instance
     {
@@ -79,35 +939,690 @@ pub fn pty_spawn(
         instances.insert(
             id.clone(),
             PtyInstance {
-                child,
+                killer,
+                exit: exit.clone(),
                 writer,
                 master: pair.master,
+                cwd: cwd.clone(),
+                title: shell_title(&shell_path),
+                pid,
+                history: history.clone(),
+                rows,
+                cols,
+                recording: recording.clone(),
+                bracketed_paste: bracketed_paste.clone(),
+                pause: pause.clone(),
             },
         );
     }
 
-    // Spawn reader thread: reads PTY output and emits events to frontend
-    let data_event = format!("pty:data:{}", id);
-    let exit_event = format!("pty:exit:{}", id);
+    pty_start_reading(
+        id.clone(),
+        app,
+        reader,
+        ReaderContext {
+            history,
+            recording,
+            exit,
+            bracketed_paste,
+            pause,
+            flush_interval_ms: state.flush_interval_ms.clone(),
+            idle_interval_ms: state.idle_interval_ms.clone(),
+        },
+    );
+
+    Ok(PtySpawnResult { id, pid })
+}
+
+/// State of a terminal shared with its reader thread and the threads it
+/// feeds.
+struct ReaderContext {
+    history: Arc<Mutex<OutputHistory>>,
+    recording: Arc<Mutex<Option<CastRecorder>>>,
+    exit: Arc<ExitSignal>,
+    bracketed_paste: Arc<AtomicBool>,
+    pause: Arc<PauseGate>,
+    /// Output batcher flush interval, shared across all terminals.
+    flush_interval_ms: Arc<AtomicU64>,
+    /// Quiet time before `pty://idle`, shared across all terminals.
+    idle_interval_ms: Arc<AtomicU64>,
+}
+
+/// Start the background reader for a freshly spawned PTY.
+///
+/// The reader thread feeds every chunk read from the master to the title
+/// parser and the bracketed-paste tracker, strips its bells (emitting
+/// `pty://bell` instead), and passes the rest to the history buffer, any
+/// active recording and the output batcher, which emits coalesced
+/// `pty://output/<id>` (base64) events. Output is
+/// pushed to the frontend as it arrives — nothing polls. Chunk sizes also go
+/// to the activity monitor behind the `pty://activity` and `pty://idle`
+/// events.
+fn pty_start_reading(
+    id: TerminalId,
+    app: AppHandle,
+    mut reader: Box<dyn Read + Send>,
+    ctx: ReaderContext,
+) {
+    let ReaderContext {
+        history,
+        recording,
+        exit,
+        bracketed_paste,
+        pause,
+        flush_interval_ms,
+        idle_interval_ms,
+    } = ctx;
+    let output_tx = spawn_output_batcher(app.clone(), id.clone(), flush_interval_ms, exit);
+    let activity_tx = spawn_activity_monitor(app.clone(), id.clone(), idle_interval_ms);
+    let title_tx = spawn_title_emitter(app.clone(), id.clone());
 
     std::thread::spawn(move || {
         let mut buf = [0u8; 4096];
+        let mut title_parser = OscTitleParser::new();
+        let mut paste_tracker = BracketedPasteTracker::new();
+        let mut bell_filter = BellFilter::new();
+        let mut last_bell: Option<Instant> = None;
         loop {
+            pause.wait();
             match reader.read(&mut buf) {
                 Ok(0) => break,
                 Ok(n) => {
-                    let text = String::from_utf8_lossy(&buf[..n]).to_string();
-                    let _ = app.emit(&data_event, PtyDataPayload { data: text });
+                    if let Some(title) = title_parser.feed(&buf[..n]) {
+                        let _ = title_tx.send(title);
+                    }
+                    if let Some(enabled) = paste_tracker.feed(&buf[..n]) {
+                        bracketed_paste.store(enabled, Ordering::Relaxed);
+                    }
+                    let _ = activity_tx.send(n);
+
+                    let (data, rang) = bell_filter.feed(&buf[..n]);
+                    if rang && last_bell.is_none_or(|at| at.elapsed() >= BELL_COALESCE) {
+                        last_bell = Some(Instant::now());
+                        let _ = app.emit("pty://bell", PtyIdPayload { id: id.clone() });
+                    }
+                    if data.is_empty() {
+                        continue;
+                    }
+
+                    if let Ok(mut h) = history.lock() {
+                        h.push(&data);
+                    }
+                    if let Ok(mut rec) = recording.lock() {
+                        if let Some(recorder) = rec.as_mut() {
+                            let _ = recorder.write_output(&data);
+                        }
+                    }
+                    if output_tx.send(data).is_err() {
+                        break;
+                    }
                 }
                 Err(_) => break,
             }
         }
-        let _ = app.emit(&exit_event, ());
+        // Close any active recording cleanly once the PTY is gone
+        if let Ok(mut rec) = recording.lock() {
+            if let Some(recorder) = rec.take() {
+                let _ = recorder.finish();
+            }
+        }
     });
+}
+
+/// Split `pending` at the last complete UTF-8 character, returning the
+/// decodable prefix and leaving any incomplete trailing sequence in place
+/// so a multi-byte character split across reads is not mangled.
+fn take_utf8_prefix(pending: &mut Vec<u8>) -> String {
+    let boundary = utf8_boundary(pending);
+    let ready: Vec<u8> = pending.drain(..boundary).collect();
+    String::from_utf8_lossy(&ready).to_string()
+}
+
+/// Length of the prefix of `data` that does not end in a truncated UTF-8
+/// sequence. Invalid bytes elsewhere are left for lossy decoding.
+fn utf8_boundary(data: &[u8]) -> usize {
+    match std::str::from_utf8(data) {
+        Ok(_) => data.len(),
+        // `error_len() == None` means the input ended mid-character.
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => data.len(),
+    }
+}
+
+/// Spawn a thread that coalesces raw PTY output into `pty://output/<id>`
+/// events.
+///
+/// Chunks are accumulated for up to the configured flush interval (or until
+/// `MAX_BATCH_BYTES` is reached) and emitted as one event. A small chunk that
+/// arrives while nothing is pending and the terminal has been idle for a full
+/// interval is emitted straight away, keeping interactive echo instant. Once
+/// the sender is dropped and the final batch is out, the batcher waits for the
+/// exit code and emits the exit event with it.
+fn spawn_output_batcher(
+    app: AppHandle,
+    id: TerminalId,
+    flush_interval_ms: Arc<AtomicU64>,
+    exit: Arc<ExitSignal>,
+) -> mpsc::Sender<Vec<u8>> {
+    let (tx, rx) = mpsc::channel::<Vec<u8>>();
+    std::thread::spawn(move || {
+        let exit_event = format!("pty:exit:{}", id);
+        let mut pending: Vec<u8> = Vec::new();
+        let mut last_flush = Instant::now();
+
+        let output_event = format!("pty://output/{}", id);
+        let emit_bytes = |bytes: &[u8]| {
+            if bytes.is_empty() {
+                return;
+            }
+            for piece in bytes.chunks(MAX_OUTPUT_CHUNK_BYTES) {
+                let _ = app.emit(&output_event, BASE64.encode(piece));
+            }
+        };
+
+        let flush = |pending: &mut Vec<u8>, last_flush: &mut Instant| {
+            let boundary = utf8_boundary(pending);
+            let ready: Vec<u8> = pending.drain(..boundary).collect();
+            emit_bytes(&ready);
+            *last_flush = Instant::now();
+        };
+
+        while let Ok(chunk) = rx.recv() {
+            let interval = Duration::from_millis(flush_interval_ms.load(Ordering::Relaxed));
+            let idle = last_flush.elapsed() >= interval;
+            pending.extend_from_slice(&chunk);
+
+            if idle && pending.len() <= SMALL_CHUNK_BYTES {
+                flush(&mut pending, &mut last_flush);
+                continue;
+            }
+
+            let deadline = Instant::now() + interval;
+            while pending.len() < MAX_BATCH_BYTES {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match rx.recv_timeout(remaining) {
+                    Ok(more) => pending.extend_from_slice(&more),
+                    Err(_) => break,
+                }
+            }
+            flush(&mut pending, &mut last_flush);
+        }
+
+        emit_bytes(&pending);
+        let exit_code = exit.wait();
+        let _ = app.emit(&exit_event, PtyExitPayload { exit_code });
+    });
+    tx
+}
+
+/// Current working directory of a running process.
+#[cfg(target_os = "linux")]
+fn process_cwd(pid: u32) -> Result<String, String> {
+    std::fs::read_link(format!("/proc/{}/cwd", pid))
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn process_cwd(pid: u32) -> Result<String, String> {
+    let mut info: libc::proc_vnodepathinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_vnodepathinfo>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDVNODEPATHINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    if ret != size {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    // `vip_path` is a MAXPATHLEN buffer, declared as nested arrays by libc.
+    let path =
+        unsafe { std::ffi::CStr::from_ptr(info.pvi_cdir.vip_path.as_ptr() as *const libc::c_char) };
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_cwd(_pid: u32) -> Result<String, String> {
+    Err("Querying the working directory is not supported on this platform".to_string())
+}
+
+/// Interval over which `pty_get_usage` samples CPU time.
+const USAGE_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One row of the system process table.
+struct ProcessStat {
+    ppid: u32,
+    /// User plus system CPU time since the process started.
+    cpu_ns: u64,
+    memory_bytes: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn process_table() -> Result<HashMap<u32, ProcessStat>, String> {
+    let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as u64;
+    let entries = std::fs::read_dir("/proc").map_err(|e| e.to_string())?;
+    let mut table = HashMap::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+            continue;
+        };
+        // The process may exit between listing and reading.
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        // `comm` (field 2) is parenthesized and may itself contain spaces or
+        // parentheses, so fields are counted from after the last `)`.
+        let Some((_, rest)) = stat.rsplit_once(')') else {
+            continue;
+        };
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        // Field `n` as numbered in proc(5).
+        let field =
+            |n: usize| -> u64 { fields.get(n - 3).and_then(|f| f.parse().ok()).unwrap_or(0) };
+        table.insert(
+            pid,
+            ProcessStat {
+                ppid: field(4) as u32,
+                cpu_ns: (field(14) + field(15)) * 1_000_000_000 / ticks_per_second,
+                memory_bytes: field(24) * page_size,
+            },
+        );
+    }
+    Ok(table)
+}
+
+#[cfg(target_os = "macos")]
+fn pid_info<T>(pid: libc::c_int, flavor: libc::c_int) -> Option<T> {
+    let mut info: T = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<T>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            flavor,
+            0,
+            &mut info as *mut T as *mut libc::c_void,
+            size,
+        )
+    };
+    (ret == size).then_some(info)
+}
+
+/// Rate of the Mach time units `proc_taskinfo` reports CPU time in: 1 GHz
+/// (nanoseconds) on Intel, 24 MHz on Apple silicon.
+#[cfg(target_os = "macos")]
+fn mach_ticks_per_second() -> u64 {
+    let mut frequency: u64 = 0;
+    let mut len = std::mem::size_of::<u64>();
+    let ret = unsafe {
+        libc::sysctlbyname(
+            b"hw.tbfrequency\0".as_ptr() as *const libc::c_char,
+            &mut frequency as *mut u64 as *mut libc::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ret == 0 && frequency > 0 {
+        frequency
+    } else {
+        1_000_000_000
+    }
+}
+
+/// Processes whose task info can't be read (other users' processes) are
+/// left out; they can't be in a terminal's tree unless it escalated.
+#[cfg(target_os = "macos")]
+fn process_table() -> Result<HashMap<u32, ProcessStat>, String> {
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    // Leave room for processes started since the count.
+    let mut pids: Vec<libc::c_int> = vec![0; count as usize + 64];
+    let size = (pids.len() * std::mem::size_of::<libc::c_int>()) as libc::c_int;
+    let count = unsafe { libc::proc_listallpids(pids.as_mut_ptr() as *mut libc::c_void, size) };
+    if count <= 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    pids.truncate(count as usize);
+
+    let ticks_per_second = mach_ticks_per_second() as u128;
+    let mut table = HashMap::new();
+    for pid in pids {
+        let bsd: Option<libc::proc_bsdinfo> = pid_info(pid, libc::PROC_PIDTBSDINFO);
+        let task: Option<libc::proc_taskinfo> = pid_info(pid, libc::PROC_PIDTASKINFO);
+        let (Some(bsd), Some(task)) = (bsd, task) else {
+            continue;
+        };
+        let ticks = (task.pti_total_user + task.pti_total_system) as u128;
+        table.insert(
+            pid as u32,
+            ProcessStat {
+                ppid: bsd.pbi_ppid,
+                cpu_ns: (ticks * 1_000_000_000 / ticks_per_second) as u64,
+                memory_bytes: task.pti_resident_size,
+            },
+        );
+    }
+    Ok(table)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_table() -> Result<HashMap<u32, ProcessStat>, String> {
+    Err("Querying process usage is not supported on this platform".to_string())
+}
+
+/// `root` and all its descendants present in `table`; empty if `root` has
+/// exited.
+fn process_tree(table: &HashMap<u32, ProcessStat>, root: u32) -> Vec<u32> {
+    if !table.contains_key(&root) {
+        return Vec::new();
+    }
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (&pid, stat) in table {
+        children.entry(stat.ppid).or_default().push(pid);
+    }
+    let mut tree = Vec::new();
+    let mut pending = vec![root];
+    while let Some(pid) = pending.pop() {
+        tree.push(pid);
+        if let Some(kids) = children.get(&pid) {
+            pending.extend(kids.iter().copied().filter(|&kid| kid != pid));
+        }
+    }
+    tree
+}
+
+/// Sum the usage of `root`'s process tree, taking CPU time as the difference
+/// between two snapshots of the process table `USAGE_SAMPLE_INTERVAL` apart.
+/// Memory and the process count come from the second snapshot.
+fn sample_tree_usage(root: u32) -> Result<PtyUsage, String> {
+    let before = process_table()?;
+    let started = Instant::now();
+    std::thread::sleep(USAGE_SAMPLE_INTERVAL);
+    let after = process_table()?;
+    let elapsed = started.elapsed();
+
+    let mut usage = PtyUsage::default();
+    let mut cpu_ns = 0;
+    for pid in process_tree(&after, root) {
+        let stat = &after[&pid];
+        // A process started during the interval used all its CPU time in it.
+        let previous = before.get(&pid).map_or(0, |p| p.cpu_ns);
+        cpu_ns += stat.cpu_ns.saturating_sub(previous);
+        usage.memory_bytes += stat.memory_bytes;
+        usage.process_count += 1;
+    }
+    usage.cpu_percent = cpu_ns as f64 * 100.0 / elapsed.as_nanos().max(1) as f64;
+    Ok(usage)
+}
+
+/// Initial tab title before the shell sets one via OSC: the shell's file name.
+fn shell_title(shell_path: &str) -> String {
+    std::path::Path::new(shell_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| shell_path.to_string())
+}
+
+#[tauri::command]
+pub async fn list_pty_sessions(
+    state: State<'_, PtyManager>,
+) -> Result<Vec<PtySessionInfo>, String> {
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut sessions: Vec<PtySessionInfo> = instances
+        .iter_mut()
+        .map(|(id, inst)| PtySessionInfo {
+            id: id.clone(),
+            cwd: live_cwd(inst).unwrap_or_else(|_| inst.cwd.clone()),
+            title: inst.title.clone(),
+            pid: inst.pid,
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(sessions)
+}
+
+/// Return the buffered recent output of a terminal so a freshly mounted view
+/// can replay it before subscribing to live `pty://output/<id>` events.
+#[tauri::command]
+pub async fn pty_get_history(id: String, state: State<'_, PtyManager>) -> Result<String, String> {
+    let history = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        instance.history.clone()
+    };
+    let data = history.lock().map_err(|e| e.to_string())?.contents();
+    Ok(String::from_utf8_lossy(&data).to_string())
+}
+
+/// Like `pty_get_history`, but also reports how much output has been evicted
+/// so the UI can mark the replayed history as truncated.
+#[tauri::command]
+pub async fn pty_get_buffer(id: String, state: State<'_, PtyManager>) -> Result<PtyBuffer, String> {
+    let history = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        instance.history.clone()
+    };
+    let history = history.lock().map_err(|e| e.to_string())?;
+    Ok(PtyBuffer {
+        data: String::from_utf8_lossy(&history.contents()).to_string(),
+        dropped_bytes: history.dropped_bytes,
+    })
+}
+
+/// Buffered output of a terminal with escape sequences stripped and control
+/// characters resolved, for find-in-terminal and copying as plain text. A
+/// view derived on demand; the raw buffer is left untouched for repainting.
+#[tauri::command]
+pub async fn pty_get_plain_buffer(
+    id: String,
+    state: State<'_, PtyManager>,
+) -> Result<PtyPlainBuffer, String> {
+    let history = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        instance.history.clone()
+    };
+    let data = history.lock().map_err(|e| e.to_string())?.contents();
+    let mut builder = PlainTextBuilder::new();
+    builder.feed(&String::from_utf8_lossy(&data));
+    Ok(builder.finish())
+}
 
+/// Start recording a terminal's output to an asciicast v2 file at `path`.
+/// Fails if a recording is already active for this terminal.
+#[tauri::command]
+pub fn pty_start_recording(
+    state: State<'_, PtyManager>,
+    id: String,
+    path: String,
+) -> Result<(), String> {
+    let instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get(&id).ok_or("Terminal not found")?;
+    let mut rec = instance.recording.lock().map_err(|e| e.to_string())?;
+    if rec.is_some() {
+        return Err("Recording already active for this terminal".to_string());
+    }
+    let recorder =
+        CastRecorder::create(&path, instance.cols, instance.rows).map_err(|e| e.to_string())?;
+    *rec = Some(recorder);
     Ok(())
 }
 
+/// Stop recording a terminal, flushing and closing the cast file.
+#[tauri::command]
+pub fn pty_stop_recording(state: State<'_, PtyManager>, id: String) -> Result<(), String> {
+    let recording = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        instance.recording.clone()
+    };
+    let recorder = recording
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or("No active recording for this terminal")?;
+    recorder.finish().map_err(|e| e.to_string())
+}
+
+/// Set the output coalescing window (milliseconds) for all terminals.
+/// Values are clamped to a range that keeps typing latency imperceptible.
+#[tauri::command]
+pub fn pty_set_flush_interval(
+    state: State<'_, PtyManager>,
+    interval_ms: u64,
+) -> Result<u64, String> {
+    let clamped = interval_ms.clamp(MIN_FLUSH_INTERVAL_MS, MAX_FLUSH_INTERVAL_MS);
+    state.flush_interval_ms.store(clamped, Ordering::Relaxed);
+    Ok(clamped)
+}
+
+/// Set how long (milliseconds) a terminal must go without output before
+/// `pty://idle` is emitted for it, for all terminals.
+#[tauri::command]
+pub fn pty_set_idle_interval(
+    state: State<'_, PtyManager>,
+    interval_ms: u64,
+) -> Result<u64, String> {
+    let clamped = interval_ms.clamp(MIN_IDLE_INTERVAL_MS, MAX_IDLE_INTERVAL_MS);
+    state.idle_interval_ms.store(clamped, Ordering::Relaxed);
+    Ok(clamped)
+}
+
+/// Working directory of the terminal's shell as it is now (after any `cd`).
+fn live_cwd(instance: &mut PtyInstance) -> Result<String, String> {
+    if instance.exit.get().is_some() {
+        return Err("Terminal process has exited".to_string());
+    }
+    let cwd = process_cwd(instance.pid)?;
+    instance.cwd = cwd.clone();
+    Ok(cwd)
+}
+
+#[tauri::command]
+pub async fn pty_get_cwd(id: String, state: State<'_, PtyManager>) -> Result<String, String> {
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get_mut(&id).ok_or("Terminal not found")?;
+    live_cwd(instance)
+}
+
+/// Resolve with the exit code once the terminal's process terminates.
+/// Rejects immediately if the id is unknown.
+#[tauri::command]
+pub async fn pty_wait(id: String, state: State<'_, PtyManager>) -> Result<u32, String> {
+    let exit = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        instance.exit.clone()
+    };
+    tauri::async_runtime::spawn_blocking(move || exit.wait())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// OS process id of a terminal's shell, as returned by `pty_spawn`, for
+/// tooling that needs to target the process itself.
+#[tauri::command]
+pub fn pty_get_pid(id: String, state: State<'_, PtyManager>) -> Result<u32, String> {
+    let instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get(&id).ok_or("Terminal not found")?;
+    Ok(instance.pid)
+}
+
+/// CPU and memory use of a terminal's process tree. Samples CPU time over a
+/// short interval, so this takes about `USAGE_SAMPLE_INTERVAL` to return.
+/// All zeros once the shell has exited.
+#[tauri::command]
+pub async fn pty_get_usage(id: String, state: State<'_, PtyManager>) -> Result<PtyUsage, String> {
+    let (pid, exit) = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let instance = instances.get(&id).ok_or("Terminal not found")?;
+        (instance.pid, instance.exit.clone())
+    };
+    if pid == 0 || exit.get().is_some() {
+        return Ok(PtyUsage::default());
+    }
+    tauri::async_runtime::spawn_blocking(move || sample_tree_usage(pid))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn pty_list(state: State<'_, PtyManager>) -> Result<Vec<PtyInfo>, String> {
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut list: Vec<PtyInfo> = instances
+        .iter_mut()
+        .map(|(id, inst)| PtyInfo {
+            id: id.clone(),
+            pid: inst.pid,
+            alive: inst.exit.get().is_none(),
+            cols: inst.cols,
+            rows: inst.rows,
+            cwd: live_cwd(inst).unwrap_or_else(|_| inst.cwd.clone()),
+        })
+        .collect();
+    list.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(list)
+}
+
+/// Deliver a signal to the terminal's foreground job (falling back to the
+/// shell itself when the foreground process group is unknown).
+#[cfg(unix)]
+fn send_signal(instance: &mut PtyInstance, signal: &str) -> Result<(), String> {
+    use nix::sys::signal::{kill, killpg, Signal};
+    use nix::unistd::Pid;
+
+    let sig = match signal {
+        "SIGINT" => Signal::SIGINT,
+        "SIGTERM" => Signal::SIGTERM,
+        "SIGHUP" => Signal::SIGHUP,
+        "SIGKILL" => Signal::SIGKILL,
+        other => return Err(format!("Unknown signal: {other}")),
+    };
+    match instance.master.process_group_leader() {
+        Some(pgrp) if pgrp > 0 => killpg(Pid::from_raw(pgrp), sig),
+        _ => kill(Pid::from_raw(instance.pid as i32), sig),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Windows has no POSIX signals. SIGINT is delivered as ETX (Ctrl+C) through
+/// the pseudo console, which ConPTY turns into a `CTRL_C_EVENT` for the
+/// attached processes; the terminating signals kill the child outright.
+#[cfg(windows)]
+fn send_signal(instance: &mut PtyInstance, signal: &str) -> Result<(), String> {
+    match signal {
+        "SIGINT" => {
+            instance
+                .writer
+                .write_all(b"\x03")
+                .map_err(|e| e.to_string())?;
+            instance.writer.flush().map_err(|e| e.to_string())
+        }
+        "SIGTERM" | "SIGHUP" | "SIGKILL" => instance.killer.kill().map_err(|e| e.to_string()),
+        other => Err(format!("Unknown signal: {other}")),
+    }
+}
+
+/// Send `SIGINT`, `SIGTERM`, `SIGHUP` or `SIGKILL` to a terminal's process.
+#[tauri::command]
+pub async fn pty_send_signal(
+    id: String,
+    signal: String,
+    state: State<'_, PtyManager>,
+) -> Result<(), String> {
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get_mut(&id).ok_or("Terminal not found")?;
+    send_signal(instance, &signal)
+}
+
 #[tauri::command]
 pub fn pty_write(state: State<'_, PtyManager>, id: String, data: String) -> Result<(), String> {
     let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
@@ -120,6 +1635,46 @@ pub fn pty_write(state: State<'_, PtyManager>, id: String, data: String) -> Resu
     Ok(())
 }
 
+/// Write pasted text to the terminal. While the program has bracketed paste
+/// enabled the text is wrapped in `ESC [ 200 ~` / `ESC [ 201 ~`, so a pasted
+/// multi-line script is inserted as a whole rather than run line by line;
+/// otherwise it is written as-is, like `pty_write`.
+#[tauri::command]
+pub fn pty_paste(state: State<'_, PtyManager>, id: String, text: String) -> Result<(), String> {
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get_mut(&id).ok_or("Terminal not found")?;
+    let payload = if instance.bracketed_paste.load(Ordering::Relaxed) {
+        // An end marker inside the text would let the rest run as commands.
+        let text = text.replace("\x1b[201~", "");
+        [PASTE_START, text.as_bytes(), PASTE_END].concat()
+    } else {
+        text.into_bytes()
+    };
+    instance
+        .writer
+        .write_all(&payload)
+        .map_err(|e| e.to_string())?;
+    instance.writer.flush().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Pause or resume reading a terminal's output, so the frontend can apply
+/// backpressure when its render queue backs up. While paused the child is
+/// throttled by the full PTY buffer instead of output piling up in memory;
+/// nothing is dropped or reordered, and output already read is still
+/// delivered. Resuming continues where reading stopped.
+#[tauri::command]
+pub fn pty_set_paused(
+    state: State<'_, PtyManager>,
+    id: String,
+    paused: bool,
+) -> Result<(), String> {
+    let instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get(&id).ok_or("Terminal not found")?;
+    instance.pause.set(paused);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn pty_resize(
     state: State<'_, PtyManager>,
@@ -127,8 +1682,8 @@ pub fn pty_resize(
     rows: u16,
     cols: u16,
 ) -> Result<(), String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&id).ok_or("Terminal not found")?;
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get_mut(&id).ok_or("Terminal not found")?;
     instance
         .master
         .resize(PtySize {
@@ -138,6 +1693,8 @@ pub fn pty_resize(
             pixel_height: 0,
         })
         .map_err(|e| e.to_string())?;
+    instance.rows = rows;
+    instance.cols = cols;
     Ok(())
 }
 
@@ -145,16 +1702,43 @@ pub fn pty_resize(
 pub fn pty_kill(state: State<'_, PtyManager>, id: String) -> Result<(), String> {
     let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
     if let Some(mut instance) = instances.remove(&id) {
-        let _ = instance.child.kill();
+        let _ = instance.killer.kill();
     }
     Ok(())
 }
 
 /// Kill all PTY instances — called on app exit
-pub fn kill_all(state: &PtyManager) {
-    if let Ok(mut instances) = state.instances.lock() {
-        for (_, mut inst) in instances.drain() {
-            let _ = inst.child.kill();
+///
+/// On Unix every shell and its foreground job first get SIGHUP (what closing
+/// a terminal sends) and up to `grace` to exit; whatever is still running is
+/// then killed.
+#[cfg_attr(not(unix), allow(unused_mut))]
+pub fn kill_all(state: &PtyManager, grace: Duration) {
+    let mut instances: Vec<PtyInstance> = match state.instances.lock() {
+        Ok(mut map) => map.drain().map(|(_, inst)| inst).collect(),
+        Err(_) => return,
+    };
+
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        for inst in instances.iter_mut() {
+            let _ = send_signal(inst, "SIGHUP");
+            let _ = kill(Pid::from_raw(inst.pid as i32), Signal::SIGHUP);
+        }
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline && instances.iter().any(|inst| inst.exit.get().is_none()) {
+            std::thread::sleep(Duration::from_millis(25));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = grace;
+
+    for mut inst in instances {
+        if inst.exit.get().is_none() {
+            let _ = inst.killer.kill();
         }
     }
 }
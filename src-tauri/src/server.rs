@@ -0,0 +1,422 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+/// Name of the server binary bundled as a Tauri sidecar.
+const SIDECAR_NAME: &str = "funny-server";
+
+/// How long a stopping server gets to exit on its own before it is killed.
+const RESTART_GRACE: Duration = Duration::from_millis(500);
+
+/// Port the sidecar (the runtime) listens on unless configured otherwise
+/// (see `RUNNER_PORT` in packages/runtime/src/index.ts).
+const DEFAULT_PORT: u16 = 3003;
+
+/// How long `server_get_port` waits for the sidecar to announce its port.
+const PORT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stdout line prefix the sidecar prints once it is listening.
+const PORT_ANNOUNCEMENT: &str = "LISTENING_ON_PORT=";
+
+/// Health-check timeout used when the caller doesn't pass one.
+const DEFAULT_HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Readiness probe timeout used by `server_status`.
+const STATUS_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Number of recent sidecar output lines kept for `server_get_log`.
+const LOG_CAPACITY: usize = 1000;
+
+pub struct ServerProcess(pub std::sync::Mutex<Option<CommandChild>>);
+
+/// Ring buffer of the most recent stdout/stderr lines from the sidecar, kept
+/// so startup failures can be diagnosed in production builds.
+pub struct ServerLog(Mutex<VecDeque<String>>);
+
+impl ServerLog {
+    pub fn new() -> Self {
+        Self(Mutex::new(VecDeque::with_capacity(LOG_CAPACITY)))
+    }
+
+    fn push(&self, line: String) {
+        if let Ok(mut lines) = self.0.lock() {
+            if lines.len() >= LOG_CAPACITY {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+}
+
+/// Where the sidecar is in announcing its listening port.
+enum PortStatus {
+    Pending,
+    Listening(u16),
+    /// The sidecar exited before announcing a port (e.g. the port was taken).
+    Failed(String),
+}
+
+/// Port the sidecar should bind (`requested`, 0 = any free port) and the port
+/// it actually announced via `LISTENING_ON_PORT=<n>`. The announced status is
+/// reset on every (re)spawn.
+pub struct ServerPort {
+    requested: Mutex<u16>,
+    status: Mutex<PortStatus>,
+    changed: Condvar,
+}
+
+impl ServerPort {
+    /// Requested port comes from `FUNNY_SERVER_PORT`, defaulting to 3003.
+    pub fn from_env() -> Self {
+        let requested = std::env::var("FUNNY_SERVER_PORT")
+            .ok()
+            .and_then(|v| v.parse::<u16>().ok())
+            .unwrap_or(DEFAULT_PORT);
+        Self {
+            requested: Mutex::new(requested),
+            status: Mutex::new(PortStatus::Pending),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn requested(&self) -> u16 {
+        self.requested.lock().map(|p| *p).unwrap_or(DEFAULT_PORT)
+    }
+
+    fn set_requested(&self, port: u16) {
+        if let Ok(mut guard) = self.requested.lock() {
+            *guard = port;
+        }
+    }
+
+    fn set(&self, status: PortStatus) {
+        if let Ok(mut guard) = self.status.lock() {
+            *guard = status;
+            self.changed.notify_all();
+        }
+    }
+
+    fn get(&self) -> Option<u16> {
+        match *self.status.lock().ok()? {
+            PortStatus::Listening(port) => Some(port),
+            _ => None,
+        }
+    }
+
+    /// Block until the port is announced, the sidecar fails, or `timeout`
+    /// elapses.
+    fn wait(&self, timeout: Duration) -> Result<u16, String> {
+        let guard = self.status.lock().map_err(|e| e.to_string())?;
+        let (guard, _) = self
+            .changed
+            .wait_timeout_while(guard, timeout, |status| {
+                matches!(status, PortStatus::Pending)
+            })
+            .map_err(|e| e.to_string())?;
+        match &*guard {
+            PortStatus::Listening(port) => Ok(*port),
+            PortStatus::Failed(reason) => Err(reason.clone()),
+            PortStatus::Pending => Err("Timed out waiting for the server port".to_string()),
+        }
+    }
+}
+
+/// Spawn the server sidecar and return its child handle. Its stdout and
+/// stderr are split into lines, forwarded as `server://stdout` /
+/// `server://stderr` and `server://log` events, and recorded in `ServerLog`.
+/// Termination is reported as `server://exit` with the exit code.
+pub fn spawn_sidecar(app: &AppHandle) -> Result<CommandChild, String> {
+    let requested_port = app
+        .try_state::<ServerPort>()
+        .map(|state| state.requested())
+        .unwrap_or(DEFAULT_PORT);
+    let sidecar = app
+        .shell()
+        .sidecar(SIDECAR_NAME)
+        .map_err(|e| format!("failed to create sidecar command: {e}"))?
+        .env("RUNNER_PORT", requested_port.to_string());
+
+    if let Some(port) = app.try_state::<ServerPort>() {
+        port.set(PortStatus::Pending);
+    }
+
+    let (mut rx, child) = sidecar
+        .spawn()
+        .map_err(|e| format!("failed to spawn server sidecar: {e}"))?;
+
+    let app = app.clone();
+    let pid = child.pid();
+    tauri::async_runtime::spawn(async move {
+        let mut stdout = LineBuffer::default();
+        let mut stderr = LineBuffer::default();
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(bytes) => {
+                    for line in stdout.push(&bytes) {
+                        handle_line(&app, "stdout", line);
+                    }
+                }
+                CommandEvent::Stderr(bytes) => {
+                    for line in stderr.push(&bytes) {
+                        handle_line(&app, "stderr", line);
+                    }
+                }
+                CommandEvent::Terminated(payload) => {
+                    if let Some(line) = stdout.finish() {
+                        handle_line(&app, "stdout", line);
+                    }
+                    if let Some(line) = stderr.finish() {
+                        handle_line(&app, "stderr", line);
+                    }
+                    forget_child(&app, pid, payload.code);
+                    let _ = app.emit("server://exit", payload.code);
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Ok(child)
+}
+
+/// Splits a byte stream into complete lines, holding back a trailing partial
+/// line until the rest of it arrives.
+#[derive(Default)]
+struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(idx) = self.pending.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.pending.drain(..=idx).collect();
+            lines.push(decode_line(&raw));
+        }
+        lines
+    }
+
+    /// Whatever is left once the stream has ended.
+    fn finish(&mut self) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let raw = std::mem::take(&mut self.pending);
+        Some(decode_line(&raw))
+    }
+}
+
+fn decode_line(raw: &[u8]) -> String {
+    String::from_utf8_lossy(raw)
+        .trim_end_matches(['\r', '\n'])
+        .to_string()
+}
+
+#[derive(Clone, Serialize)]
+struct ServerLogPayload {
+    stream: &'static str,
+    line: String,
+}
+
+/// Process one complete line of sidecar output: pick up the port
+/// announcement, record it in `ServerLog`, and forward it to the frontend as
+/// `server://<stream>` and `server://log` events.
+fn handle_line(app: &AppHandle, stream: &'static str, line: String) {
+    if let Some(value) = line.strip_prefix(PORT_ANNOUNCEMENT) {
+        if let (Ok(port), Some(state)) =
+            (value.trim().parse::<u16>(), app.try_state::<ServerPort>())
+        {
+            state.set(PortStatus::Listening(port));
+        }
+    }
+    if let Some(log) = app.try_state::<ServerLog>() {
+        log.push(line.clone());
+    }
+    let _ = app.emit(&format!("server://{stream}"), line.clone());
+    let _ = app.emit("server://log", ServerLogPayload { stream, line });
+}
+
+/// Drop the stored child handle once that process has exited, unless it has
+/// already been replaced by a restart. If it never announced a port, waiters
+/// in `server_get_port` are failed immediately instead of timing out.
+fn forget_child(app: &AppHandle, pid: u32, code: Option<i32>) {
+    let Some(state) = app.try_state::<ServerProcess>() else {
+        return;
+    };
+    let Ok(mut guard) = state.0.lock() else {
+        return;
+    };
+    if guard.as_ref().map(|child| child.pid()) == Some(pid) {
+        *guard = None;
+        if let Some(port) = app.try_state::<ServerPort>() {
+            let reason = match port.get() {
+                Some(_) => "Server exited".to_string(),
+                None => format!(
+                    "Server exited (code {}) before listening on port {}",
+                    code.map(|c| c.to_string())
+                        .unwrap_or_else(|| "unknown".into()),
+                    port.requested()
+                ),
+            };
+            port.set(PortStatus::Failed(reason));
+        }
+    }
+}
+
+/// True while a process with `pid` still exists.
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), None).is_ok()
+}
+
+/// Without a cheap liveness probe, rely on the `Terminated` event clearing
+/// the stored child (see `forget_child`).
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    true
+}
+
+/// Ask the server to stop with SIGTERM, wait up to `grace` for it to exit,
+/// then SIGKILL it if it is still around.
+#[cfg(unix)]
+fn terminate(child: CommandChild, grace: Duration) {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    let pid = child.pid();
+    if kill(Pid::from_raw(pid as i32), Signal::SIGTERM).is_ok() {
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline {
+            if !is_running(pid) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(25));
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Windows has no SIGTERM equivalent for a console-less child; kill directly.
+#[cfg(not(unix))]
+fn terminate(child: CommandChild, _grace: Duration) {
+    let _ = child.kill();
+}
+
+/// Stop the running server sidecar (if any) and launch a fresh one, optionally
+/// on a different port (0 = any free port).
+#[tauri::command]
+pub async fn server_restart(
+    app: AppHandle,
+    state: State<'_, ServerProcess>,
+    port: Option<u16>,
+) -> Result<(), String> {
+    if let Some(port) = port {
+        app.state::<ServerPort>().set_requested(port);
+    }
+    let old = state.0.lock().map_err(|e| e.to_string())?.take();
+    if let Some(child) = old {
+        tauri::async_runtime::spawn_blocking(move || terminate(child, RESTART_GRACE))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let child = spawn_sidecar(&app)?;
+    *state.0.lock().map_err(|e| e.to_string())? = Some(child);
+    Ok(())
+}
+
+/// Recent sidecar output, oldest first.
+#[tauri::command]
+pub async fn server_get_log(state: State<'_, ServerLog>) -> Result<Vec<String>, String> {
+    let lines = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(lines.iter().cloned().collect())
+}
+
+/// Port the server sidecar is actually listening on, waiting up to five
+/// seconds for it to be announced after (re)start. Fails right away if the
+/// sidecar exited without binding (e.g. the port was already in use).
+#[tauri::command]
+pub async fn server_get_port(app: AppHandle) -> Result<u16, String> {
+    tauri::async_runtime::spawn_blocking(move || app.state::<ServerPort>().wait(PORT_WAIT_TIMEOUT))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Ping the server's health endpoint. Resolves `true` on a 200 response and
+/// `false` when the server is unreachable or doesn't answer within the
+/// timeout; only non-network failures (e.g. client setup) are errors.
+#[tauri::command]
+pub async fn server_health_check(app: AppHandle, timeout_ms: Option<u32>) -> Result<bool, String> {
+    let timeout = timeout_ms
+        .map(|ms| Duration::from_millis(ms as u64))
+        .unwrap_or(DEFAULT_HEALTH_TIMEOUT);
+    let port = app
+        .try_state::<ServerPort>()
+        .and_then(|state| state.get())
+        .unwrap_or(DEFAULT_PORT);
+    check_health(port, timeout).await
+}
+
+async fn check_health(port: u16, timeout: Duration) -> Result<bool, String> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let url = format!("http://127.0.0.1:{}/api/health", port);
+    match client.get(&url).send().await {
+        Ok(response) => Ok(response.status() == reqwest::StatusCode::OK),
+        Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[derive(Serialize)]
+pub struct ServerStatus {
+    /// The sidecar process is running.
+    alive: bool,
+    /// Port announced by the sidecar, once known.
+    port: Option<u16>,
+    /// The health endpoint answered on that port.
+    ready: bool,
+}
+
+/// Combined liveness/readiness snapshot so the UI can hold its first
+/// requests until the server is actually listening.
+#[tauri::command]
+pub async fn server_status(
+    process: State<'_, ServerProcess>,
+    port: State<'_, ServerPort>,
+) -> Result<ServerStatus, String> {
+    let alive = match process.0.lock().map_err(|e| e.to_string())?.as_ref() {
+        Some(child) => is_running(child.pid()),
+        None => false,
+    };
+    let port = port.get();
+    let ready = match (alive, port) {
+        (true, Some(p)) => check_health(p, STATUS_PROBE_TIMEOUT).await.unwrap_or(false),
+        _ => false,
+    };
+    Ok(ServerStatus { alive, port, ready })
+}
+
+/// Stop the server sidecar gracefully, killing it if it hasn't exited
+/// within `grace` — called on app exit.
+pub fn shutdown(app: &AppHandle, grace: Duration) {
+    let Some(state) = app.try_state::<ServerProcess>() else {
+        return;
+    };
+    let child = match state.0.lock() {
+        Ok(mut guard) => guard.take(),
+        Err(_) => None,
+    };
+    if let Some(child) = child {
+        terminate(child, grace);
+    }
+}